@@ -0,0 +1,145 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::hint::black_box;
+
+use alloy_primitives::{Address, Log, LogData, B256, U256};
+use alloy_sol_types::{SolEvent, SolEventInterface};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use recall_sol_facade::{blobs, bucket};
+
+const NUM_LOGS: usize = 1_000;
+
+fn subscriber(i: usize) -> Address {
+    Address::with_last_byte((i % 16) as u8)
+}
+
+fn blob_added_logs() -> Vec<Log> {
+    (0..NUM_LOGS)
+        .map(|i| {
+            let event = blobs::BlobAdded {
+                subscriber: subscriber(i),
+                hash: B256::repeat_byte(i as u8),
+                size: U256::from(i * 1024),
+                expiry: U256::from(i + 3600),
+                bytesUsed: U256::from(i * 4096),
+            };
+            Log {
+                address: Address::ZERO,
+                data: event.encode_log_data(),
+            }
+        })
+        .collect()
+}
+
+fn object_added_logs() -> Vec<Log> {
+    (0..NUM_LOGS)
+        .map(|i| {
+            let event = bucket::ObjectAdded {
+                key: format!("photos/2025/{i:06}.jpg").into_bytes().into(),
+                blobHash: B256::repeat_byte(i as u8),
+                metadata: vec![0xa1, 0x61, 0x6b, 0x61, 0x76].into(),
+            };
+            Log {
+                address: Address::ZERO,
+                data: event.encode_log_data(),
+            }
+        })
+        .collect()
+}
+
+fn bench_blob_added(c: &mut Criterion) {
+    let logs = blob_added_logs();
+    let target = subscriber(3);
+
+    let mut group = c.benchmark_group("BlobAdded");
+    group.throughput(Throughput::Elements(NUM_LOGS as u64));
+
+    group.bench_function("Events::decode_log/sum_size", |b| {
+        b.iter(|| {
+            let mut total = U256::ZERO;
+            for log in &logs {
+                if let Ok(Log {
+                    data: blobs::Events::BlobAdded(e),
+                    ..
+                }) = blobs::Events::decode_log(black_box(log), false)
+                {
+                    total += e.size;
+                }
+            }
+            total
+        })
+    });
+    group.bench_function("LazyEvent/sum_size", |b| {
+        b.iter(|| {
+            let mut total = U256::ZERO;
+            for log in &logs {
+                if let Some(blobs::lazy::LazyEvent::BlobAdded(v)) =
+                    blobs::lazy::LazyEvent::from_log(black_box(&log.data))
+                {
+                    total += v.size();
+                }
+            }
+            total
+        })
+    });
+
+    group.bench_function("Events::decode_log/filter_subscriber", |b| {
+        b.iter(|| {
+            logs.iter()
+                .filter_map(|log| blobs::Events::decode_log(black_box(log), false).ok())
+                .filter(|log| matches!(&log.data, blobs::Events::BlobAdded(e) if e.subscriber == target))
+                .count()
+        })
+    });
+    group.bench_function("LazyEvent/filter_subscriber", |b| {
+        b.iter(|| {
+            logs.iter()
+                .filter_map(|log| blobs::lazy::BlobAddedView::from_log(black_box(&log.data)))
+                .filter(|v| v.is_subscriber(&target))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+fn bench_object_added(c: &mut Criterion) {
+    let logs = object_added_logs();
+
+    let mut group = c.benchmark_group("ObjectAdded");
+    group.throughput(Throughput::Elements(NUM_LOGS as u64));
+
+    group.bench_function("Events::decode_log/key_len", |b| {
+        b.iter(|| {
+            let mut total = 0;
+            for log in &logs {
+                if let Ok(Log {
+                    data: bucket::Events::ObjectAdded(e),
+                    ..
+                }) = bucket::Events::decode_log(black_box(log), false)
+                {
+                    total += e.key.len();
+                }
+            }
+            total
+        })
+    });
+    group.bench_function("LazyEvent/key_len", |b| {
+        b.iter(|| {
+            let mut total = 0;
+            for log in &logs {
+                let data: &LogData = black_box(&log.data);
+                if let Some(bucket::lazy::LazyEvent::ObjectAdded(v)) =
+                    bucket::lazy::LazyEvent::from_log(data)
+                {
+                    total += v.key().map(|k| k.len()).unwrap_or_default();
+                }
+            }
+            total
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_blob_added, bench_object_added);
+criterion_main!(benches);
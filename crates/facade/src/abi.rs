@@ -0,0 +1,63 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Low-level helpers for reading ABI-encoded words without allocating.
+
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::{Error, Result};
+
+/// Size of an ABI word in bytes.
+pub(crate) const WORD: usize = 32;
+
+/// Returns the word at `index` (in words, not bytes), if the buffer is long enough.
+#[inline]
+pub(crate) fn word(data: &[u8], index: usize) -> Option<&[u8; WORD]> {
+    let start = index.checked_mul(WORD)?;
+    data.get(start..start.checked_add(WORD)?)?.try_into().ok()
+}
+
+/// Returns the word at `index` as a borrowed `B256`.
+#[inline]
+pub(crate) fn word_b256(data: &[u8], index: usize) -> Option<&B256> {
+    word(data, index).map(<&B256>::from)
+}
+
+/// Reads the word at `index` as a `U256`.
+#[inline]
+pub(crate) fn word_u256(data: &[u8], index: usize) -> Option<U256> {
+    word(data, index).map(|w| U256::from_be_bytes(*w))
+}
+
+/// Reads the word at `index` as a `bool`.
+#[inline]
+pub(crate) fn word_bool(data: &[u8], index: usize) -> Option<bool> {
+    word(data, index).map(|w| w[WORD - 1] != 0)
+}
+
+/// Reads the address stored in the low 20 bytes of a word.
+#[inline]
+pub(crate) fn word_address(word: &B256) -> Address {
+    Address::from_word(*word)
+}
+
+/// Reads a word as a `usize` offset or length, rejecting values that don't fit.
+#[inline]
+fn word_usize(data: &[u8], index_bytes: usize) -> Result<usize> {
+    let w = data
+        .get(index_bytes..index_bytes.checked_add(WORD).ok_or(Error::Overrun)?)
+        .ok_or(Error::Overrun)?;
+    let (high, low) = w.split_at(WORD - 8);
+    if high.iter().any(|&b| b != 0) {
+        return Err(Error::Overrun);
+    }
+    usize::try_from(u64::from_be_bytes(low.try_into().unwrap())).map_err(|_| Error::Overrun)
+}
+
+/// Borrows the dynamic `bytes` value whose head (offset) is stored at word `index`.
+pub(crate) fn dyn_bytes(data: &[u8], index: usize) -> Result<&[u8]> {
+    let offset = word_usize(data, index.checked_mul(WORD).ok_or(Error::Overrun)?)?;
+    let len = word_usize(data, offset)?;
+    let start = offset + WORD;
+    let end = start.checked_add(len).ok_or(Error::Overrun)?;
+    data.get(start..end).ok_or(Error::Overrun)
+}
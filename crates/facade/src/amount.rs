@@ -0,0 +1,319 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use alloy_primitives::U256;
+use anyhow::{anyhow, bail};
+use fvm_shared::{bigint::BigUint, econ::TokenAmount};
+
+use crate::types::BigUintWrapper;
+
+/// An amount of RECALL tokens, stored in atto (10^-18 RECALL).
+///
+/// Parses from strings like `"1.5 RECALL"`, `"1500000000000000000 atto"` or `"1.5"` (RECALL is
+/// the default unit). `Display` prints RECALL with trailing zeros trimmed, or with exactly the
+/// requested number of decimals, e.g. `format!("{:.2}", amount)`. Extra digits are truncated,
+/// never rounded up.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RecallAmount(U256);
+
+/// Implements the shared API of 18-decimal amount newtypes around `U256`, displayed and parsed
+/// with `$unit` as the whole unit.
+macro_rules! atto_amount {
+    ($ty:ident, $unit:literal) => {
+        impl $ty {
+            /// Number of decimals of the whole unit.
+            pub const DECIMALS: usize = 18;
+            /// Atto per whole unit.
+            pub const PRECISION: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+            pub const ZERO: $ty = $ty(U256::ZERO);
+
+            pub const fn from_atto(atto: U256) -> Self {
+                $ty(atto)
+            }
+
+            /// Panics if the amount overflows `U256`, which a `u64` can't.
+            pub fn from_whole(whole: u64) -> Self {
+                $ty(U256::from(whole) * Self::PRECISION)
+            }
+
+            pub const fn atto(&self) -> U256 {
+                self.0
+            }
+
+            pub fn is_zero(&self) -> bool {
+                self.0.is_zero()
+            }
+
+            pub fn checked_add(self, other: Self) -> Option<Self> {
+                self.0.checked_add(other.0).map($ty)
+            }
+
+            pub fn checked_sub(self, other: Self) -> Option<Self> {
+                self.0.checked_sub(other.0).map($ty)
+            }
+
+            pub fn saturating_add(self, other: Self) -> Self {
+                $ty(self.0.saturating_add(other.0))
+            }
+
+            pub fn saturating_sub(self, other: Self) -> Self {
+                $ty(self.0.saturating_sub(other.0))
+            }
+
+            /// Formats the amount in whole units without a unit.
+            ///
+            /// With `Some(precision)`, prints exactly that many decimals (truncating). With
+            /// `None`, prints all significant decimals.
+            pub fn to_decimal_string(&self, precision: Option<usize>) -> String {
+                $crate::amount::format_decimal(self.0, Self::DECIMALS, precision)
+            }
+        }
+
+        impl std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{} {}", self.to_decimal_string(f.precision()), $unit)
+            }
+        }
+
+        impl std::str::FromStr for $ty {
+            type Err = anyhow::Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $crate::amount::parse_amount(s, $unit, Self::DECIMALS).map($ty)
+            }
+        }
+
+        impl From<U256> for $ty {
+            fn from(value: U256) -> Self {
+                $ty(value)
+            }
+        }
+
+        impl From<$ty> for U256 {
+            fn from(value: $ty) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+#[cfg(feature = "credit")]
+pub(crate) use atto_amount;
+
+atto_amount!(RecallAmount, "RECALL");
+
+/// Parses `"<number> [<unit>|atto]"`, where the unit defaults to `unit`, into atto.
+pub(crate) fn parse_amount(s: &str, unit: &str, decimals: usize) -> anyhow::Result<U256> {
+    let mut parts = s.split_whitespace();
+    let number = parts.next().ok_or_else(|| anyhow!("empty amount"))?;
+    let given = parts.next();
+    if parts.next().is_some() {
+        bail!("invalid amount {:?}: expected <number> [{}|atto]", s, unit);
+    }
+    let decimals = match given {
+        None => decimals,
+        Some(u) if u.eq_ignore_ascii_case(unit) => decimals,
+        Some(u) if u.eq_ignore_ascii_case("atto") => 0,
+        Some(u) => bail!("invalid amount {:?}: unknown unit {:?}", s, u),
+    };
+    parse_decimal(number, decimals).map_err(|e| anyhow!("invalid amount {:?}: {}", s, e))
+}
+
+/// Formats `value` scaled down by 10^`decimals`, truncating to `precision` decimals if given.
+pub(crate) fn format_decimal(value: U256, decimals: usize, precision: Option<usize>) -> String {
+    let scale = U256::from(10).pow(U256::from(decimals));
+    let whole = value / scale;
+    let mut frac = if decimals == 0 {
+        String::new()
+    } else {
+        format!("{:0>width$}", (value % scale).to_string(), width = decimals)
+    };
+    match precision {
+        Some(p) if p <= decimals => frac.truncate(p),
+        Some(p) => frac.extend(std::iter::repeat_n('0', p - decimals)),
+        None => frac.truncate(frac.trim_end_matches('0').len()),
+    }
+    if frac.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, frac)
+    }
+}
+
+/// Parses a non-negative decimal number scaled by 10^`decimals`.
+pub(crate) fn parse_decimal(number: &str, decimals: usize) -> anyhow::Result<U256> {
+    let (whole, frac) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && frac.is_empty() {
+        bail!("missing digits");
+    }
+    if !whole.chars().chain(frac.chars()).all(|c| c.is_ascii_digit()) {
+        bail!("not a non-negative decimal number");
+    }
+    if frac.len() > decimals {
+        bail!("more than {} decimals", decimals);
+    }
+    let digits = format!("{}{:0<width$}", whole, frac, width = decimals);
+    U256::from_str_radix(&digits, 10).map_err(|_| anyhow!("overflows 256 bits"))
+}
+
+impl TryFrom<BigUintWrapper> for RecallAmount {
+    type Error = anyhow::Error;
+
+    fn try_from(value: BigUintWrapper) -> Result<Self, Self::Error> {
+        if value.0.bits() > 256 {
+            bail!("amount overflows 256 bits: {}", value.0);
+        }
+        Ok(RecallAmount(value.into()))
+    }
+}
+
+impl From<RecallAmount> for BigUintWrapper {
+    fn from(value: RecallAmount) -> Self {
+        BigUintWrapper::from(value.0)
+    }
+}
+
+impl TryFrom<&TokenAmount> for RecallAmount {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &TokenAmount) -> Result<Self, Self::Error> {
+        let atto: BigUint = value
+            .atto()
+            .to_biguint()
+            .ok_or_else(|| anyhow!("negative token amount: {}", value.atto()))?;
+        RecallAmount::try_from(BigUintWrapper(atto))
+    }
+}
+
+impl TryFrom<TokenAmount> for RecallAmount {
+    type Error = anyhow::Error;
+
+    fn try_from(value: TokenAmount) -> Result<Self, Self::Error> {
+        RecallAmount::try_from(&value)
+    }
+}
+
+impl From<RecallAmount> for TokenAmount {
+    fn from(value: RecallAmount) -> Self {
+        TokenAmount::from(BigUintWrapper::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> anyhow::Result<U256> {
+        s.parse::<RecallAmount>().map(|a| a.atto())
+    }
+
+    #[test]
+    fn parses_units() {
+        let one_and_a_half = U256::from(1_500_000_000_000_000_000u64);
+        assert_eq!(parse("1.5 RECALL").unwrap(), one_and_a_half);
+        assert_eq!(parse("1.5 recall").unwrap(), one_and_a_half);
+        assert_eq!(parse("1.5").unwrap(), one_and_a_half);
+        assert_eq!(parse("  1.5  ").unwrap(), one_and_a_half);
+        assert_eq!(parse("1500000000000000000 atto").unwrap(), one_and_a_half);
+        assert_eq!(parse("0").unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn parses_partial_decimals() {
+        assert_eq!(parse(".5").unwrap(), RecallAmount::PRECISION / U256::from(2));
+        assert_eq!(parse("1.").unwrap(), RecallAmount::PRECISION);
+        assert_eq!(parse("0.000000000000000001").unwrap(), U256::from(1));
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        let err = |s: &str| parse(s).unwrap_err().to_string();
+        assert_eq!(err(""), "empty amount");
+        assert_eq!(err("."), "invalid amount \".\": missing digits");
+        assert_eq!(
+            err("0.0000000000000000001"),
+            "invalid amount \"0.0000000000000000001\": more than 18 decimals"
+        );
+        assert_eq!(err("1.5 atto"), "invalid amount \"1.5 atto\": more than 0 decimals");
+        assert_eq!(err("1e3"), "invalid amount \"1e3\": not a non-negative decimal number");
+        assert_eq!(err("-1"), "invalid amount \"-1\": not a non-negative decimal number");
+        assert_eq!(err("+1"), "invalid amount \"+1\": not a non-negative decimal number");
+        assert_eq!(err("1.2.3"), "invalid amount \"1.2.3\": not a non-negative decimal number");
+        assert_eq!(err("1 FIL"), "invalid amount \"1 FIL\": unknown unit \"FIL\"");
+        assert_eq!(
+            err("1 RECALL atto"),
+            "invalid amount \"1 RECALL atto\": expected <number> [RECALL|atto]"
+        );
+    }
+
+    #[test]
+    fn rejects_overflow() {
+        let max = U256::MAX.to_string();
+        assert_eq!(parse(&format!("{} atto", max)).unwrap(), U256::MAX);
+        let over = format!("{}0 atto", max);
+        assert!(parse(&over).unwrap_err().to_string().ends_with("overflows 256 bits"));
+        // The scaled value overflows even though the whole part fits.
+        assert!(parse(&max).unwrap_err().to_string().ends_with("overflows 256 bits"));
+    }
+
+    #[test]
+    fn formats() {
+        let amount: RecallAmount = "1.5".parse().unwrap();
+        assert_eq!(amount.to_string(), "1.5 RECALL");
+        assert_eq!(format!("{:.0}", amount), "1 RECALL");
+        assert_eq!(format!("{:.3}", amount), "1.500 RECALL");
+        assert_eq!(format!("{:.20}", amount), "1.50000000000000000000 RECALL");
+        assert_eq!(RecallAmount::ZERO.to_string(), "0 RECALL");
+        assert_eq!(RecallAmount::from_whole(7).to_string(), "7 RECALL");
+
+        // Truncates rather than rounding up.
+        let atto = RecallAmount::PRECISION * U256::from(2) - U256::from(1);
+        let almost_two = RecallAmount::from_atto(atto);
+        assert_eq!(almost_two.to_decimal_string(None), "1.999999999999999999");
+        assert_eq!(almost_two.to_decimal_string(Some(2)), "1.99");
+    }
+
+    #[test]
+    fn format_decimal_without_decimals() {
+        assert_eq!(format_decimal(U256::from(42), 0, None), "42");
+        assert_eq!(format_decimal(U256::from(42), 0, Some(2)), "42.00");
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in ["0", "1", "0.000000000000000001", "123.456", "1000000"] {
+            let amount: RecallAmount = s.parse().unwrap();
+            assert_eq!(amount.to_decimal_string(None), s);
+            assert_eq!(amount.to_string().parse::<RecallAmount>().unwrap(), amount);
+        }
+        let max = RecallAmount::from_atto(U256::MAX);
+        assert_eq!(max.to_string().parse::<RecallAmount>().unwrap(), max);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let one = RecallAmount::from_whole(1);
+        let max = RecallAmount::from_atto(U256::MAX);
+        assert_eq!(one.checked_add(one), Some(RecallAmount::from_whole(2)));
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(RecallAmount::ZERO.checked_sub(one), None);
+        assert_eq!(max.saturating_add(one), max);
+        assert_eq!(RecallAmount::ZERO.saturating_sub(one), RecallAmount::ZERO);
+        assert!(one.saturating_sub(one).is_zero());
+    }
+
+    #[test]
+    fn converts() {
+        let amount: RecallAmount = "2.25".parse().unwrap();
+        let token = TokenAmount::from(amount);
+        assert_eq!(token, TokenAmount::from_atto(2_250_000_000_000_000_000u64));
+        assert_eq!(RecallAmount::try_from(&token).unwrap(), amount);
+        let wrapper = BigUintWrapper::from(amount);
+        assert_eq!(RecallAmount::try_from(wrapper).unwrap(), amount);
+        assert_eq!(<U256 as From<_>>::from(amount), amount.atto());
+
+        assert!(RecallAmount::try_from(TokenAmount::from_atto(-1)).is_err());
+        let wide = BigUintWrapper(BigUint::from(1u8) << 256);
+        assert!(RecallAmount::try_from(wide).is_err());
+    }
+}
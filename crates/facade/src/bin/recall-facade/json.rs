@@ -0,0 +1,91 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Rendering of decoded ABI values as JSON.
+//!
+//! Integers that fit in 64 bits are JSON numbers, wider ones are decimal strings. Byte values are
+//! `0x`-prefixed hex. Tuples with named components (i.e. structs) become objects.
+
+use alloy_dyn_abi::DynSolValue;
+use alloy_json_abi::{EventParam, Param};
+use alloy_primitives::hex;
+use serde_json::{json, Map, Value};
+
+/// The name and struct components of a parameter.
+pub trait ParamInfo {
+    fn name(&self) -> &str;
+    fn components(&self) -> &[Param];
+}
+
+impl ParamInfo for Param {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn components(&self) -> &[Param] {
+        &self.components
+    }
+}
+
+impl ParamInfo for EventParam {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn components(&self) -> &[Param] {
+        &self.components
+    }
+}
+
+/// Renders a list of values as an object keyed by parameter name.
+pub fn params<P: ParamInfo>(params: &[P], values: &[DynSolValue]) -> Value {
+    let mut obj = Map::new();
+    for (i, (param, value)) in params.iter().zip(values).enumerate() {
+        let key = if param.name().is_empty() {
+            format!("_{}", i)
+        } else {
+            param.name().to_string()
+        };
+        obj.insert(key, self::value(value, param.components()));
+    }
+    Value::Object(obj)
+}
+
+/// Renders a single value. `components` names tuple fields (or array element tuple fields).
+pub fn value(value: &DynSolValue, components: &[Param]) -> Value {
+    match value {
+        DynSolValue::Bool(b) => json!(b),
+        DynSolValue::Int(i, bits) => match i64::try_from(*i) {
+            Ok(n) if *bits <= 64 => json!(n),
+            _ => json!(i.to_string()),
+        },
+        DynSolValue::Uint(u, bits) => match u64::try_from(*u) {
+            Ok(n) if *bits <= 64 => json!(n),
+            _ => json!(u.to_string()),
+        },
+        DynSolValue::FixedBytes(word, size) => json!(hex::encode_prefixed(&word[..*size])),
+        DynSolValue::Address(address) => json!(address.to_checksum(None)),
+        DynSolValue::Function(function) => json!(hex::encode_prefixed(function.as_slice())),
+        DynSolValue::Bytes(bytes) => json!(hex::encode_prefixed(bytes)),
+        DynSolValue::String(s) => json!(s),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            Value::Array(values.iter().map(|v| self::value(v, components)).collect())
+        }
+        DynSolValue::Tuple(values) => {
+            if !components.is_empty() && components.iter().all(|c| !c.name.is_empty()) {
+                params(components, values)
+            } else {
+                Value::Array(
+                    values
+                        .iter()
+                        .enumerate()
+                        .map(|(i, v)| {
+                            let inner = components.get(i).map_or(&[][..], |c| &c.components);
+                            self::value(v, inner)
+                        })
+                        .collect(),
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,419 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Encode and decode Recall facade calldata, return data and logs.
+
+use std::io::{ErrorKind, Read, Write};
+
+use alloy_dyn_abi::{EventExt, FunctionExt, JsonAbiExt, Specifier};
+use alloy_json_abi::Function;
+use alloy_primitives::{hex, Selector, B256};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand};
+use serde_json::{json, Value};
+
+use registry::Registry;
+
+mod json;
+mod registry;
+
+#[derive(Parser)]
+#[command(name = "recall-facade", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Encode a facade function call as calldata.
+    Encode {
+        /// Facade name, e.g. `blobs` or `bucket`.
+        facade: String,
+        /// Function name, or full signature to pick an overload, e.g. `queryObjects(string)`.
+        function: String,
+        /// Function arguments, e.g. `0x...` for addresses and bytes, `[(k,v)]` for structs.
+        args: Vec<String>,
+    },
+    /// Decode calldata, return data or logs into JSON.
+    Decode {
+        #[command(subcommand)]
+        target: DecodeTarget,
+    },
+    /// List all function selectors and event topics.
+    List {
+        /// Print JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DecodeTarget {
+    /// Decode calldata. The facade and function are found by selector.
+    Calldata {
+        /// Hex calldata, a JSON transaction with an `input` or `data` field, or `-` for stdin.
+        input: String,
+    },
+    /// Decode function return data.
+    Output {
+        /// Facade name, e.g. `blobs` or `bucket`.
+        facade: String,
+        /// Function name or full signature.
+        function: String,
+        /// Hex return data, or `-` for stdin.
+        input: String,
+    },
+    /// Decode logs. The facade and event are found by topic.
+    Log {
+        /// A JSON log, an array of logs, or a receipt with `logs`; hex data if `--topic` is
+        /// given; or `-` for stdin.
+        input: String,
+        /// Log topic, in order. May be repeated.
+        #[arg(long = "topic")]
+        topics: Vec<String>,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let registry = Registry::load()?;
+    let output = match cli.command {
+        Command::Encode {
+            facade,
+            function,
+            args,
+        } => {
+            let function = registry.function(&facade, &function, args.len())?;
+            let calldata = encode(function, &args)?;
+            println!("{}", hex::encode_prefixed(calldata));
+            return Ok(());
+        }
+        Command::Decode { target } => match target {
+            DecodeTarget::Calldata { input } => decode_calldata(&registry, &input)?,
+            DecodeTarget::Output {
+                facade,
+                function,
+                input,
+            } => decode_output(&registry, &facade, &function, &input)?,
+            DecodeTarget::Log { input, topics } => decode_logs(&registry, &input, &topics)?,
+        },
+        Command::List { json } => {
+            if json {
+                list_json(&registry)
+            } else {
+                return list_table(&registry);
+            }
+        }
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+fn encode(function: &Function, args: &[String]) -> Result<Vec<u8>> {
+    if args.len() != function.inputs.len() {
+        bail!("expected {} arguments, got {}", function.inputs.len(), args.len());
+    }
+    let values = function
+        .inputs
+        .iter()
+        .zip(args)
+        .map(|(param, arg)| {
+            let ty = param.resolve()?;
+            ty.coerce_str(arg)
+                .with_context(|| format!("invalid {} value for {}: {}", ty, param.name, arg))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(function.abi_encode_input(&values)?)
+}
+
+fn decode_calldata(registry: &Registry, input: &str) -> Result<Value> {
+    let input = read_input(input)?;
+    let calldata = if is_json(&input) {
+        let tx: Value = serde_json::from_str(&input)?;
+        let field = tx
+            .get("input")
+            .or_else(|| tx.get("data"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("transaction has no input or data field"))?;
+        parse_hex(field)?
+    } else {
+        parse_hex(&input)?
+    };
+    if calldata.len() < 4 {
+        bail!("calldata is shorter than a selector");
+    }
+    let selector = Selector::from_slice(&calldata[..4]);
+    for (facade, function) in registry.functions_by_selector(selector) {
+        if let Ok(values) = function.abi_decode_input(&calldata[4..], true) {
+            return Ok(json!({
+                "facade": facade.name,
+                "function": function.name,
+                "signature": function.signature(),
+                "selector": selector,
+                "inputs": json::params(&function.inputs, &values),
+            }));
+        }
+    }
+    bail!("no facade function matches selector {}", selector)
+}
+
+fn decode_output(
+    registry: &Registry,
+    facade: &str,
+    function: &str,
+    input: &str,
+) -> Result<Value> {
+    let data = parse_hex(&read_input(input)?)?;
+    let candidates: Vec<&Function> = if function.contains('(') {
+        let f = registry.function(facade, function, 0)?;
+        vec![f]
+    } else {
+        registry
+            .facade(facade)?
+            .abi
+            .function(function)
+            .map(|fs| fs.iter().collect())
+            .unwrap_or_default()
+    };
+    // Overloads usually share return types, so try each until one decodes.
+    for function in candidates {
+        if let Ok(values) = function.abi_decode_output(&data, true) {
+            return Ok(json!({
+                "facade": facade,
+                "function": function.name,
+                "outputs": json::params(&function.outputs, &values),
+            }));
+        }
+    }
+    bail!("return data does not decode as {} facade function {}", facade, function)
+}
+
+fn decode_logs(registry: &Registry, input: &str, topics: &[String]) -> Result<Value> {
+    let input = read_input(input)?;
+    if !topics.is_empty() {
+        let topics = topics.iter().map(|t| parse_topic(t)).collect::<Result<Vec<_>>>()?;
+        return decode_log(registry, &topics, &parse_hex(&input)?);
+    }
+    if !is_json(&input) {
+        bail!("hex log data requires at least one --topic");
+    }
+    let value: Value = serde_json::from_str(&input)?;
+    let logs = match &value {
+        Value::Array(logs) => logs.clone(),
+        Value::Object(obj) => match obj.get("logs") {
+            Some(Value::Array(logs)) => logs.clone(),
+            _ => return decode_log_json(registry, &value),
+        },
+        _ => bail!("expected a log, an array of logs or a receipt"),
+    };
+    let decoded = logs
+        .iter()
+        .map(|log| decode_log_json(registry, log))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Value::Array(decoded))
+}
+
+fn decode_log_json(registry: &Registry, log: &Value) -> Result<Value> {
+    let topics = log
+        .get("topics")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("log has no topics"))?
+        .iter()
+        .map(|t| parse_topic(t.as_str().unwrap_or_default()))
+        .collect::<Result<Vec<_>>>()?;
+    let data = parse_hex(log.get("data").and_then(Value::as_str).unwrap_or("0x"))?;
+    let mut decoded = decode_log(registry, &topics, &data)?;
+    if let (Some(address), Value::Object(obj)) = (log.get("address"), &mut decoded) {
+        obj.insert("address".into(), address.clone());
+    }
+    Ok(decoded)
+}
+
+fn decode_log(registry: &Registry, topics: &[B256], data: &[u8]) -> Result<Value> {
+    let topic = *topics.first().ok_or_else(|| anyhow!("log has no topics"))?;
+    for (facade, event) in registry.events_by_topic(topic) {
+        if let Ok(decoded) = event.decode_log_parts(topics.iter().copied(), data, true) {
+            // Restore declaration order from the separately decoded indexed and body values.
+            let mut indexed = decoded.indexed.into_iter();
+            let mut body = decoded.body.into_iter();
+            let values: Vec<_> = event
+                .inputs
+                .iter()
+                .filter_map(|p| if p.indexed { indexed.next() } else { body.next() })
+                .collect();
+            return Ok(json!({
+                "facade": facade.name,
+                "event": event.name,
+                "signature": event.signature(),
+                "topic": topic,
+                "params": json::params(&event.inputs, &values),
+            }));
+        }
+    }
+    bail!("no facade event matches topic {}", topic)
+}
+
+fn list_json(registry: &Registry) -> Value {
+    let facades = registry
+        .facades
+        .iter()
+        .map(|facade| {
+            let functions: Vec<_> = facade
+                .abi
+                .functions()
+                .map(|f| json!({ "selector": f.selector(), "signature": f.signature() }))
+                .collect();
+            let events: Vec<_> = facade
+                .abi
+                .events()
+                .map(|e| json!({ "topic": e.selector(), "signature": e.signature() }))
+                .collect();
+            json!({ "facade": facade.name, "functions": functions, "events": events })
+        })
+        .collect();
+    Value::Array(facades)
+}
+
+fn list_table(registry: &Registry) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    let mut write = || -> std::io::Result<()> {
+        for facade in &registry.facades {
+            for f in facade.abi.functions() {
+                writeln!(out, "{:<12} function {} {}", facade.name, f.selector(), f.signature())?;
+            }
+            for e in facade.abi.events() {
+                writeln!(out, "{:<12} event    {} {}", facade.name, e.selector(), e.signature())?;
+            }
+        }
+        out.flush()
+    };
+    match write() {
+        // The reader exited early, e.g. `recall-facade list | head`.
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+fn read_input(input: &str) -> Result<String> {
+    if input == "-" {
+        let mut buf = String::new();
+        std::io::stdin().read_to_string(&mut buf)?;
+        Ok(buf.trim().to_string())
+    } else {
+        Ok(input.trim().to_string())
+    }
+}
+
+fn is_json(input: &str) -> bool {
+    input.starts_with('{') || input.starts_with('[')
+}
+
+fn parse_hex(input: &str) -> Result<Vec<u8>> {
+    hex::decode(input.trim()).with_context(|| format!("invalid hex: {}", input))
+}
+
+fn parse_topic(input: &str) -> Result<B256> {
+    input
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid topic: {}", input))
+}
+
+#[cfg(all(test, feature = "blobs", feature = "bucket"))]
+mod tests {
+    use alloy_primitives::Address;
+    use alloy_sol_types::{SolCall, SolEvent};
+    use recall_sol_facade::{blobs, bucket};
+
+    use super::*;
+
+    #[test]
+    fn calldata_round_trip() {
+        let registry = Registry::load().unwrap();
+        let args = [
+            "0x0000000000000000000000000000000000000000000000000000000000000001".to_string(),
+            "photos/a.png".to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000002".to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000003".to_string(),
+            "42".to_string(),
+            "0".to_string(),
+            "[(type,image/png)]".to_string(),
+            "true".to_string(),
+        ];
+        let function = registry.function("bucket", "addObject", args.len()).unwrap();
+        let calldata = encode(function, &args).unwrap();
+
+        let call = bucket::addObject_1Call::abi_decode(&calldata, true).unwrap();
+        assert_eq!(call.key, "photos/a.png");
+        assert_eq!(call.size, 42);
+        assert_eq!(call.metadata[0].value, "image/png");
+        assert!(call.overwrite);
+
+        let decoded = decode_calldata(&registry, &hex::encode_prefixed(&calldata)).unwrap();
+        assert_eq!(decoded["facade"], "bucket");
+        assert_eq!(decoded["function"], "addObject");
+        assert_eq!(decoded["inputs"]["key"], "photos/a.png");
+        assert_eq!(decoded["inputs"]["size"], 42);
+        assert_eq!(decoded["inputs"]["metadata"], json!([{ "key": "type", "value": "image/png" }]));
+
+        let tx = json!({ "input": hex::encode_prefixed(&calldata) }).to_string();
+        assert_eq!(decode_calldata(&registry, &tx).unwrap(), decoded);
+        assert!(decode_calldata(&registry, "0x12345678").is_err());
+
+        assert_eq!(
+            encode(function, &args[..7]).unwrap_err().to_string(),
+            "expected 8 arguments, got 7"
+        );
+        let extra = [&args[..], &["1".to_string()]].concat();
+        assert_eq!(
+            encode(function, &extra).unwrap_err().to_string(),
+            "expected 8 arguments, got 9"
+        );
+    }
+
+    #[test]
+    fn output_round_trip() {
+        let registry = Registry::load().unwrap();
+        let query = bucket::Query {
+            objects: Vec::new(),
+            commonPrefixes: vec!["a/".to_string()],
+            nextKey: "b".to_string(),
+        };
+        let data = bucket::queryObjects_2Call::abi_encode_returns(&(query,));
+        let hex = hex::encode_prefixed(data);
+        let decoded = decode_output(&registry, "bucket", "queryObjects", &hex).unwrap();
+        assert_eq!(decoded["function"], "queryObjects");
+        assert_eq!(decoded["outputs"]["_0"]["commonPrefixes"], json!(["a/"]));
+        assert_eq!(decoded["outputs"]["_0"]["nextKey"], "b");
+        assert!(decode_output(&registry, "bucket", "getObject", "0x00").is_err());
+    }
+
+    #[test]
+    fn log_round_trip() {
+        let registry = Registry::load().unwrap();
+        let event = blobs::BlobFinalized {
+            subscriber: Address::repeat_byte(0x11),
+            hash: B256::repeat_byte(0x22),
+            resolved: true,
+        };
+        let log = event.encode_log_data();
+        let topics: Vec<String> = log.topics().iter().map(|t| t.to_string()).collect();
+        let data = hex::encode_prefixed(&log.data);
+
+        let decoded = decode_logs(&registry, &data, &topics).unwrap();
+        assert_eq!(decoded["facade"], "blobs");
+        assert_eq!(decoded["event"], "BlobFinalized");
+        assert_eq!(decoded["topic"], json!(blobs::BlobFinalized::SIGNATURE_HASH));
+        assert_eq!(decoded["params"]["subscriber"], json!(event.subscriber.to_checksum(None)));
+        assert_eq!(decoded["params"]["hash"], json!(event.hash));
+        assert_eq!(decoded["params"]["resolved"], true);
+
+        let receipt = json!({
+            "logs": [{ "address": "0x01", "topics": topics, "data": data }],
+        });
+        let decoded = decode_logs(&registry, &receipt.to_string(), &[]).unwrap();
+        assert_eq!(decoded[0]["event"], "BlobFinalized");
+        assert_eq!(decoded[0]["address"], "0x01");
+    }
+}
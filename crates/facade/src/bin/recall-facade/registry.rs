@@ -0,0 +1,123 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! JSON ABIs of all facades.
+//!
+//! The generated bindings embed the JSON ABI they were generated from in their module docs.
+//! We read it from there, so the CLI can never disagree with the bindings.
+
+use alloy_json_abi::{Event, Function, JsonAbi};
+use alloy_primitives::{Selector, B256};
+use anyhow::{anyhow, bail, Context, Result};
+
+const SOURCES: &[(&str, &str)] = &[
+    (
+        "blob-reader",
+        include_str!("../../blobreader_facade/iblobreaderfacade.rs"),
+    ),
+    ("blobs", include_str!("../../blobs_facade/iblobsfacade.rs")),
+    ("bucket", include_str!("../../bucket_facade/ibucketfacade.rs")),
+    ("config", include_str!("../../config_facade/iconfigfacade.rs")),
+    ("credit", include_str!("../../credit_facade/icreditfacade.rs")),
+    ("gas", include_str!("../../gas_facade/igasfacade.rs")),
+    ("machine", include_str!("../../machine_facade/imachinefacade.rs")),
+    ("timehub", include_str!("../../timehub_facade/itimehubfacade.rs")),
+];
+
+pub struct Facade {
+    pub name: &'static str,
+    pub abi: JsonAbi,
+}
+
+pub struct Registry {
+    pub facades: Vec<Facade>,
+}
+
+impl Registry {
+    pub fn load() -> Result<Self> {
+        let facades = SOURCES
+            .iter()
+            .map(|(name, source)| {
+                let abi = serde_json::from_str(extract_json(source)?)
+                    .with_context(|| format!("invalid {} facade ABI", name))?;
+                Ok(Facade { name, abi })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { facades })
+    }
+
+    pub fn facade(&self, name: &str) -> Result<&Facade> {
+        self.facades
+            .iter()
+            .find(|f| f.name == name)
+            .ok_or_else(|| {
+                let names: Vec<_> = self.facades.iter().map(|f| f.name).collect();
+                anyhow!("unknown facade {}; expected one of {}", name, names.join(", "))
+            })
+    }
+
+    /// Finds a function by name or full signature. Overloads are resolved by argument count.
+    pub fn function(&self, facade: &str, name: &str, num_args: usize) -> Result<&Function> {
+        let facade = self.facade(facade)?;
+        let candidates: Vec<&Function> = if name.contains('(') {
+            facade.abi.functions().filter(|f| f.signature() == name).collect()
+        } else {
+            facade
+                .abi
+                .function(name)
+                .map(|fs| fs.iter().filter(|f| f.inputs.len() == num_args).collect())
+                .unwrap_or_default()
+        };
+        match candidates.as_slice() {
+            [function] => Ok(function),
+            [] => bail!(
+                "no {} facade function {} taking {} argument(s)",
+                facade.name,
+                name,
+                num_args
+            ),
+            _ => {
+                let sigs: Vec<_> = candidates.iter().map(|f| f.signature()).collect();
+                bail!("{} is ambiguous; use one of {}", name, sigs.join(", "))
+            }
+        }
+    }
+
+    pub fn functions_by_selector(&self, selector: Selector) -> Vec<(&Facade, &Function)> {
+        self.facades
+            .iter()
+            .flat_map(|facade| {
+                facade
+                    .abi
+                    .functions()
+                    .filter(move |f| f.selector() == selector)
+                    .map(move |f| (facade, f))
+            })
+            .collect()
+    }
+
+    pub fn events_by_topic(&self, topic: B256) -> Vec<(&Facade, &Event)> {
+        self.facades
+            .iter()
+            .flat_map(|facade| {
+                facade
+                    .abi
+                    .events()
+                    .filter(move |e| e.selector() == topic)
+                    .map(move |e| (facade, e))
+            })
+            .collect()
+    }
+}
+
+fn extract_json(source: &str) -> Result<&str> {
+    const START: &str = "```json\n";
+    let start = source
+        .find(START)
+        .ok_or_else(|| anyhow!("missing JSON ABI"))?
+        + START.len();
+    let len = source[start..]
+        .find("```")
+        .ok_or_else(|| anyhow!("unterminated JSON ABI"))?;
+    Ok(&source[start..start + len])
+}
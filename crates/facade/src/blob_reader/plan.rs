@@ -0,0 +1,312 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use alloy_primitives::U256;
+use anyhow::{anyhow, bail};
+
+use super::ReadRequestOpened;
+
+/// A contiguous range of a blob, read by one request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReadRange {
+    /// Position of the range in its [`ReadPlan`].
+    pub index: usize,
+    pub offset: u64,
+    pub length: u64,
+}
+
+impl ReadRange {
+    /// The offset just past the range.
+    pub fn end(&self) -> u64 {
+        self.offset + self.length
+    }
+}
+
+/// Splits a read of `length` bytes at `offset` of a blob into ranges of at most `max_chunk`
+/// bytes.
+///
+/// The ranges are in order, don't overlap and exactly cover the requested read. Reads that are
+/// empty or extend past the blob size (i.e. `Blob.size`) are rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadPlan {
+    blob_size: u64,
+    offset: u64,
+    length: u64,
+    ranges: Vec<ReadRange>,
+}
+
+impl ReadPlan {
+    pub fn new(
+        blob_size: u64,
+        offset: U256,
+        length: U256,
+        max_chunk: u64,
+    ) -> anyhow::Result<Self> {
+        if max_chunk == 0 {
+            bail!("invalid read plan: maximum chunk size is zero");
+        }
+        let offset = u64::try_from(offset)
+            .map_err(|_| anyhow!("invalid read: offset {} overflows u64", offset))?;
+        let length = u64::try_from(length)
+            .map_err(|_| anyhow!("invalid read: length {} overflows u64", length))?;
+        if length == 0 {
+            bail!("invalid read: length is zero");
+        }
+        match offset.checked_add(length) {
+            Some(end) if end <= blob_size => {}
+            _ => bail!(
+                "invalid read: {} bytes at offset {} exceed the blob size of {} bytes",
+                length,
+                offset,
+                blob_size
+            ),
+        }
+        let ranges = (0..length.div_ceil(max_chunk))
+            .map(|i| {
+                let start = i * max_chunk;
+                ReadRange {
+                    index: i as usize,
+                    offset: offset + start,
+                    length: max_chunk.min(length - start),
+                }
+            })
+            .collect();
+        Ok(Self {
+            blob_size,
+            offset,
+            length,
+            ranges,
+        })
+    }
+
+    /// Plans the read requested by a `ReadRequestOpened` event.
+    pub fn from_request(
+        request: &ReadRequestOpened,
+        blob_size: u64,
+        max_chunk: u64,
+    ) -> anyhow::Result<Self> {
+        Self::new(blob_size, request.readOffset, request.readLength, max_chunk)
+    }
+
+    pub fn blob_size(&self) -> u64 {
+        self.blob_size
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    pub fn ranges(&self) -> &[ReadRange] {
+        &self.ranges
+    }
+
+    /// Returns an assembler for the payloads of this plan's ranges.
+    pub fn assembler(&self) -> Assembler {
+        Assembler {
+            ranges: self.ranges.clone(),
+            parts: vec![None; self.ranges.len()],
+        }
+    }
+}
+
+/// Collects the callback payloads of a [`ReadPlan`]'s ranges, in any order, and joins them.
+#[derive(Clone, Debug)]
+pub struct Assembler {
+    ranges: Vec<ReadRange>,
+    parts: Vec<Option<Vec<u8>>>,
+}
+
+impl Assembler {
+    /// Adds the payload for the range at `index`. Fails if the index is unknown, the range was
+    /// already received, or the payload length doesn't match the range.
+    pub fn insert(&mut self, index: usize, data: Vec<u8>) -> anyhow::Result<()> {
+        let range = self
+            .ranges
+            .get(index)
+            .ok_or_else(|| anyhow!("unknown read range {}", index))?;
+        if data.len() as u64 != range.length {
+            bail!(
+                "read range {} at offset {} expects {} bytes, got {}",
+                index,
+                range.offset,
+                range.length,
+                data.len()
+            );
+        }
+        let part = &mut self.parts[index];
+        if part.is_some() {
+            bail!("read range {} at offset {} was already received", index, range.offset);
+        }
+        *part = Some(data);
+        Ok(())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.parts.iter().all(Option::is_some)
+    }
+
+    /// Iterates over the ranges that haven't been received yet.
+    pub fn missing(&self) -> impl Iterator<Item = &ReadRange> {
+        self.ranges
+            .iter()
+            .zip(&self.parts)
+            .filter(|(_, part)| part.is_none())
+            .map(|(range, _)| range)
+    }
+
+    /// Joins the payloads in order. Fails if any range is missing.
+    pub fn finish(self) -> anyhow::Result<Vec<u8>> {
+        if let Some(range) = self.missing().next() {
+            bail!(
+                "read range {} at offset {} is missing",
+                range.index,
+                range.offset
+            );
+        }
+        let len = self.ranges.iter().map(|r| r.length as usize).sum();
+        let mut out = Vec::with_capacity(len);
+        for part in self.parts.into_iter().flatten() {
+            out.extend(part);
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, B256};
+
+    use super::*;
+
+    fn plan(blob_size: u64, offset: u64, length: u64, max_chunk: u64) -> anyhow::Result<ReadPlan> {
+        ReadPlan::new(blob_size, U256::from(offset), U256::from(length), max_chunk)
+    }
+
+    fn bounds(plan: &ReadPlan) -> Vec<(usize, u64, u64)> {
+        plan.ranges().iter().map(|r| (r.index, r.offset, r.length)).collect()
+    }
+
+    #[test]
+    fn chunks_exact_multiples() {
+        let plan = plan(100, 10, 30, 10).unwrap();
+        assert_eq!(bounds(&plan), [(0, 10, 10), (1, 20, 10), (2, 30, 10)]);
+        assert_eq!((plan.blob_size(), plan.offset(), plan.length()), (100, 10, 30));
+        assert_eq!(plan.ranges().last().unwrap().end(), 40);
+    }
+
+    #[test]
+    fn chunks_with_remainder() {
+        let remainder = plan(100, 0, 25, 10).unwrap();
+        assert_eq!(bounds(&remainder), [(0, 0, 10), (1, 10, 10), (2, 20, 5)]);
+        // A chunk larger than the read is one range.
+        assert_eq!(bounds(&plan(100, 99, 1, 64).unwrap()), [(0, 99, 1)]);
+        let whole = plan(u64::MAX, 0, u64::MAX, u64::MAX / 2).unwrap();
+        let last = whole.ranges()[2];
+        assert_eq!((whole.ranges().len(), last.length, last.end()), (3, 1, u64::MAX));
+    }
+
+    #[test]
+    fn rejects_reads_past_blob_size() {
+        assert!(plan(100, 0, 100, 10).is_ok());
+        assert_eq!(
+            plan(100, 50, 51, 10).unwrap_err().to_string(),
+            "invalid read: 51 bytes at offset 50 exceed the blob size of 100 bytes"
+        );
+        assert!(plan(100, 100, 1, 10).is_err());
+        // The end overflows u64.
+        assert!(plan(u64::MAX, u64::MAX, 1, 10).is_err());
+    }
+
+    #[test]
+    fn rejects_zero_length_and_chunk() {
+        assert_eq!(plan(100, 0, 0, 10).unwrap_err().to_string(), "invalid read: length is zero");
+        assert_eq!(
+            plan(100, 0, 10, 0).unwrap_err().to_string(),
+            "invalid read plan: maximum chunk size is zero"
+        );
+    }
+
+    #[test]
+    fn rejects_values_above_u64() {
+        let big = U256::from(u64::MAX) + U256::from(1);
+        assert_eq!(
+            ReadPlan::new(100, big, U256::from(1), 10).unwrap_err().to_string(),
+            format!("invalid read: offset {} overflows u64", big)
+        );
+        assert_eq!(
+            ReadPlan::new(100, U256::ZERO, U256::MAX, 10).unwrap_err().to_string(),
+            format!("invalid read: length {} overflows u64", U256::MAX)
+        );
+    }
+
+    #[test]
+    fn plans_requests() {
+        let request = ReadRequestOpened {
+            id: B256::ZERO,
+            blobHash: B256::ZERO,
+            readOffset: U256::from(5),
+            readLength: U256::from(15),
+            callbackAddress: Address::ZERO,
+            callbackMethod: U256::ZERO,
+        };
+        let plan = ReadPlan::from_request(&request, 20, 8).unwrap();
+        assert_eq!(bounds(&plan), [(0, 5, 8), (1, 13, 7)]);
+        assert!(ReadPlan::from_request(&request, 19, 8).is_err());
+    }
+
+    #[test]
+    fn assembles_out_of_order() {
+        let plan = plan(100, 0, 25, 10).unwrap();
+        let mut assembler = plan.assembler();
+        assert!(!assembler.is_complete());
+        assembler.insert(2, vec![2; 5]).unwrap();
+        assembler.insert(0, vec![0; 10]).unwrap();
+        let missing: Vec<_> = assembler.missing().map(|r| r.index).collect();
+        assert_eq!(missing, [1]);
+        assembler.insert(1, vec![1; 10]).unwrap();
+        assert!(assembler.is_complete());
+        assert_eq!(assembler.missing().count(), 0);
+        let data = assembler.finish().unwrap();
+        assert_eq!(data, [vec![0; 10], vec![1; 10], vec![2; 5]].concat());
+    }
+
+    #[test]
+    fn rejects_bad_chunks() {
+        let plan = plan(100, 0, 25, 10).unwrap();
+        let mut assembler = plan.assembler();
+        assembler.insert(1, vec![1; 10]).unwrap();
+        assert_eq!(
+            assembler.insert(1, vec![1; 10]).unwrap_err().to_string(),
+            "read range 1 at offset 10 was already received"
+        );
+        assert_eq!(
+            assembler.insert(2, vec![2; 10]).unwrap_err().to_string(),
+            "read range 2 at offset 20 expects 5 bytes, got 10"
+        );
+        assert_eq!(
+            assembler.insert(3, vec![]).unwrap_err().to_string(),
+            "unknown read range 3"
+        );
+        // Rejected payloads aren't kept.
+        let missing: Vec<_> = assembler.missing().map(|r| r.index).collect();
+        assert_eq!(missing, [0, 2]);
+    }
+
+    #[test]
+    fn finish_fails_with_missing_chunks() {
+        let plan = plan(100, 0, 25, 10).unwrap();
+        let mut assembler = plan.assembler();
+        assembler.insert(0, vec![0; 10]).unwrap();
+        assembler.insert(2, vec![2; 5]).unwrap();
+        assert_eq!(
+            assembler.clone().finish().unwrap_err().to_string(),
+            "read range 1 at offset 10 is missing"
+        );
+        assembler.insert(1, vec![1; 10]).unwrap();
+        assert_eq!(assembler.finish().unwrap().len(), 25);
+    }
+}
@@ -0,0 +1,332 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::HashMap;
+use std::fmt;
+
+use alloy_primitives::{Address, B256, U256};
+use anyhow::{anyhow, bail};
+
+use super::{Events, ReadRequestOpened};
+
+/// The status of a blob read request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReadStatus {
+    /// Opened, waiting to be picked up.
+    Open,
+    /// Picked up, waiting for the read to be delivered.
+    Pending,
+    /// Delivered to the callback, or otherwise closed.
+    Closed,
+}
+
+impl fmt::Display for ReadStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ReadStatus::Open => "open",
+            ReadStatus::Pending => "pending",
+            ReadStatus::Closed => "closed",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A blob read request, correlated from its events.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadRequest {
+    pub id: B256,
+    pub blob_hash: B256,
+    pub offset: U256,
+    pub length: U256,
+    pub callback_address: Address,
+    pub callback_method: U256,
+    pub status: ReadStatus,
+    /// Block of the `ReadRequestOpened` event.
+    pub opened_at: u64,
+    /// Block of the `ReadRequestPending` event.
+    pub pending_at: Option<u64>,
+    /// Block of the `ReadRequestClosed` event.
+    pub closed_at: Option<u64>,
+}
+
+impl ReadRequest {
+    fn opened(event: &ReadRequestOpened, block: u64) -> Self {
+        Self {
+            id: event.id,
+            blob_hash: event.blobHash,
+            offset: event.readOffset,
+            length: event.readLength,
+            callback_address: event.callbackAddress,
+            callback_method: event.callbackMethod,
+            status: ReadStatus::Open,
+            opened_at: block,
+            pending_at: None,
+            closed_at: None,
+        }
+    }
+}
+
+/// Tracks blob read requests by applying blob reader events in order.
+///
+/// A request moves from open to pending to closed; open requests may also be closed directly.
+/// Events for unknown requests and any other transition are rejected with an error. Requests
+/// that stay pending for more than [`max_pending_blocks`](Self::max_pending_blocks) are
+/// reported by [`stalled`](Self::stalled), which is any pending request for the default tracker.
+#[derive(Clone, Debug, Default)]
+pub struct ReadTracker {
+    requests: HashMap<B256, ReadRequest>,
+    max_pending_blocks: u64,
+}
+
+impl ReadTracker {
+    pub fn new(max_pending_blocks: u64) -> Self {
+        Self {
+            requests: HashMap::new(),
+            max_pending_blocks,
+        }
+    }
+
+    pub fn max_pending_blocks(&self) -> u64 {
+        self.max_pending_blocks
+    }
+
+    pub fn get(&self, id: B256) -> Option<&ReadRequest> {
+        self.requests.get(&id)
+    }
+
+    /// Iterates over all tracked requests with the given status.
+    pub fn with_status(&self, status: ReadStatus) -> impl Iterator<Item = &ReadRequest> {
+        self.requests.values().filter(move |r| r.status == status)
+    }
+
+    pub fn open(&self) -> impl Iterator<Item = &ReadRequest> {
+        self.with_status(ReadStatus::Open)
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &ReadRequest> {
+        self.with_status(ReadStatus::Pending)
+    }
+
+    pub fn closed(&self) -> impl Iterator<Item = &ReadRequest> {
+        self.with_status(ReadStatus::Closed)
+    }
+
+    /// Iterates over pending requests that have been pending for more than
+    /// `max_pending_blocks` as of `current_block`.
+    pub fn stalled(&self, current_block: u64) -> impl Iterator<Item = &ReadRequest> {
+        let max = self.max_pending_blocks;
+        self.pending().filter(move |r| {
+            r.pending_at
+                .is_some_and(|at| current_block.saturating_sub(at) > max)
+        })
+    }
+
+    /// Forgets closed requests that were closed before `block`.
+    pub fn prune_closed(&mut self, block: u64) {
+        self.requests
+            .retain(|_, r| r.status != ReadStatus::Closed || r.closed_at >= Some(block));
+    }
+
+    /// Applies a blob reader event emitted in `block`.
+    pub fn apply(&mut self, event: &Events, block: u64) -> anyhow::Result<&ReadRequest> {
+        match event {
+            Events::ReadRequestOpened(e) => {
+                if let Some(existing) = self.requests.get(&e.id) {
+                    bail!(
+                        "illegal ReadRequestOpened for read request {}: request is already {}",
+                        e.id,
+                        existing.status
+                    );
+                }
+                Ok(self
+                    .requests
+                    .entry(e.id)
+                    .or_insert_with(|| ReadRequest::opened(e, block)))
+            }
+            Events::ReadRequestPending(e) => {
+                let request = self.tracked(e.id, "ReadRequestPending")?;
+                if request.status != ReadStatus::Open {
+                    bail!(
+                        "illegal ReadRequestPending for read request {}: request is {}, \
+                         but only open requests can become pending",
+                        e.id,
+                        request.status
+                    );
+                }
+                request.status = ReadStatus::Pending;
+                request.pending_at = Some(block);
+                Ok(request)
+            }
+            Events::ReadRequestClosed(e) => {
+                let request = self.tracked(e.id, "ReadRequestClosed")?;
+                if request.status == ReadStatus::Closed {
+                    bail!(
+                        "illegal ReadRequestClosed for read request {}: request is already closed",
+                        e.id
+                    );
+                }
+                request.status = ReadStatus::Closed;
+                request.closed_at = Some(block);
+                Ok(request)
+            }
+        }
+    }
+
+    fn tracked(&mut self, id: B256, event: &str) -> anyhow::Result<&mut ReadRequest> {
+        self.requests.get_mut(&id).ok_or_else(|| {
+            anyhow!(
+                "illegal {} for read request {}: request was never opened",
+                event,
+                id
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blob_reader::{ReadRequestClosed, ReadRequestPending};
+
+    fn id(n: u8) -> B256 {
+        B256::repeat_byte(n)
+    }
+
+    fn opened(n: u8) -> Events {
+        Events::ReadRequestOpened(ReadRequestOpened {
+            id: id(n),
+            blobHash: B256::repeat_byte(0xbb),
+            readOffset: U256::from(1),
+            readLength: U256::from(2),
+            callbackAddress: Address::repeat_byte(0xcc),
+            callbackMethod: U256::from(3),
+        })
+    }
+
+    fn pending(n: u8) -> Events {
+        Events::ReadRequestPending(ReadRequestPending { id: id(n) })
+    }
+
+    fn closed(n: u8) -> Events {
+        Events::ReadRequestClosed(ReadRequestClosed { id: id(n) })
+    }
+
+    fn ids<'a>(requests: impl Iterator<Item = &'a ReadRequest>) -> Vec<B256> {
+        let mut ids: Vec<_> = requests.map(|r| r.id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn default_tracker_is_empty() {
+        let tracker = ReadTracker::default();
+        assert_eq!(tracker.max_pending_blocks(), 0);
+        assert!(tracker.get(id(1)).is_none());
+        assert_eq!(tracker.open().count(), 0);
+    }
+
+    #[test]
+    fn opens_then_fulfills_then_closes() {
+        let mut tracker = ReadTracker::new(10);
+        let request = tracker.apply(&opened(1), 5).unwrap().clone();
+        assert_eq!(request.status, ReadStatus::Open);
+        assert_eq!(
+            (request.blob_hash, request.offset, request.length),
+            (B256::repeat_byte(0xbb), U256::from(1), U256::from(2))
+        );
+        assert_eq!(
+            (request.callback_address, request.callback_method),
+            (Address::repeat_byte(0xcc), U256::from(3))
+        );
+        assert_eq!((request.opened_at, request.pending_at, request.closed_at), (5, None, None));
+        assert_eq!(ids(tracker.open()), [id(1)]);
+
+        let request = tracker.apply(&pending(1), 6).unwrap();
+        assert_eq!((request.status, request.pending_at), (ReadStatus::Pending, Some(6)));
+        assert!(ids(tracker.open()).is_empty());
+        assert_eq!(ids(tracker.pending()), [id(1)]);
+
+        let request = tracker.apply(&closed(1), 7).unwrap();
+        assert_eq!((request.status, request.closed_at), (ReadStatus::Closed, Some(7)));
+        assert_eq!(request.pending_at, Some(6));
+        assert!(ids(tracker.pending()).is_empty());
+        assert_eq!(ids(tracker.closed()), [id(1)]);
+    }
+
+    #[test]
+    fn closes_open_requests_directly() {
+        let mut tracker = ReadTracker::default();
+        tracker.apply(&opened(1), 1).unwrap();
+        let request = tracker.apply(&closed(1), 2).unwrap();
+        assert_eq!((request.status, request.pending_at), (ReadStatus::Closed, None));
+    }
+
+    #[test]
+    fn rejects_illegal_transitions() {
+        let mut tracker = ReadTracker::default();
+        let err = |tracker: &mut ReadTracker, event: &Events| {
+            tracker.apply(event, 9).err().unwrap().to_string()
+        };
+        let never_opened = format!("request {}: request was never opened", id(1));
+        assert!(err(&mut tracker, &pending(1)).ends_with(&never_opened));
+        assert!(err(&mut tracker, &closed(1)).ends_with(&never_opened));
+
+        tracker.apply(&opened(1), 1).unwrap();
+        assert!(err(&mut tracker, &opened(1)).ends_with("request is already open"));
+        tracker.apply(&pending(1), 2).unwrap();
+        assert!(err(&mut tracker, &pending(1)).contains("request is pending, but only open"));
+        assert!(err(&mut tracker, &opened(1)).ends_with("request is already pending"));
+        tracker.apply(&closed(1), 3).unwrap();
+        assert!(err(&mut tracker, &closed(1)).ends_with("request is already closed"));
+        assert!(err(&mut tracker, &pending(1)).contains("request is closed, but only open"));
+        assert!(err(&mut tracker, &opened(1)).ends_with("request is already closed"));
+
+        // Rejected events leave the request as it was.
+        let request = tracker.get(id(1)).unwrap();
+        assert_eq!((request.status, request.closed_at), (ReadStatus::Closed, Some(3)));
+    }
+
+    #[test]
+    fn reports_stalled_requests() {
+        let mut tracker = ReadTracker::new(2);
+        for n in 1..=3 {
+            tracker.apply(&opened(n), 1).unwrap();
+        }
+        tracker.apply(&pending(1), 10).unwrap();
+        tracker.apply(&pending(2), 11).unwrap();
+        assert!(ids(tracker.stalled(12)).is_empty());
+        assert_eq!(ids(tracker.stalled(13)), [id(1)]);
+        assert_eq!(ids(tracker.stalled(14)), [id(1), id(2)]);
+        // A clock behind the events stalls nothing.
+        assert!(ids(tracker.stalled(0)).is_empty());
+
+        tracker.apply(&closed(1), 14).unwrap();
+        assert_eq!(ids(tracker.stalled(14)), [id(2)]);
+
+        let mut tracker = ReadTracker::default();
+        tracker.apply(&opened(1), 1).unwrap();
+        tracker.apply(&pending(1), 1).unwrap();
+        assert!(ids(tracker.stalled(1)).is_empty());
+        assert_eq!(ids(tracker.stalled(2)), [id(1)]);
+    }
+
+    #[test]
+    fn prunes_closed_requests() {
+        let mut tracker = ReadTracker::default();
+        for n in 1..=3 {
+            tracker.apply(&opened(n), 1).unwrap();
+        }
+        tracker.apply(&closed(1), 5).unwrap();
+        tracker.apply(&closed(2), 6).unwrap();
+        tracker.prune_closed(6);
+        assert!(tracker.get(id(1)).is_none());
+        assert_eq!(ids(tracker.closed()), [id(2)]);
+        assert_eq!(ids(tracker.open()), [id(3)]);
+        // Pruned requests are forgotten: they can't be closed again, and reopening them starts
+        // from a fresh open state.
+        assert!(tracker.apply(&closed(1), 7).is_err());
+        let reopened = tracker.apply(&opened(1), 8).unwrap();
+        assert_eq!(reopened.status, ReadStatus::Open);
+        assert_eq!((reopened.opened_at, reopened.closed_at), (8, None));
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::SolEvent;
+
+use super::{BlobAdded, BlobDeleted, BlobFinalized, BlobPending};
+use crate::filter::{address_topic, EventFilter, FilterEvent, TopicIndex};
+
+/// A blobs facade event kind, used to select events in a [`BlobsFilter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    BlobAdded,
+    BlobPending,
+    BlobFinalized,
+    BlobDeleted,
+}
+
+impl FilterEvent for EventKind {
+    const ALL: &'static [Self] = &[
+        EventKind::BlobAdded,
+        EventKind::BlobPending,
+        EventKind::BlobFinalized,
+        EventKind::BlobDeleted,
+    ];
+
+    fn signature_hash(&self) -> B256 {
+        match self {
+            EventKind::BlobAdded => BlobAdded::SIGNATURE_HASH,
+            EventKind::BlobPending => BlobPending::SIGNATURE_HASH,
+            EventKind::BlobFinalized => BlobFinalized::SIGNATURE_HASH,
+            EventKind::BlobDeleted => BlobDeleted::SIGNATURE_HASH,
+        }
+    }
+}
+
+/// Typed `eth_getLogs` filter builder for blobs facade events.
+pub type BlobsFilter = EventFilter<EventKind>;
+
+/// Returns a log filter builder for blobs facade events.
+///
+/// All blobs events index `subscriber` as their first topic.
+pub fn filter() -> BlobsFilter {
+    BlobsFilter::default()
+}
+
+impl BlobsFilter {
+    /// Matches logs with the given indexed subscriber.
+    pub fn subscriber(self, subscriber: Address) -> Self {
+        self.topic(TopicIndex::First, address_topic(&subscriber))
+    }
+
+    /// Matches logs with any of the given indexed subscribers.
+    pub fn subscribers(self, subscribers: impl IntoIterator<Item = Address>) -> Self {
+        subscribers.into_iter().fold(self, Self::subscriber)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn selects_all_events_by_default() {
+        let subscriber = Address::repeat_byte(0x11);
+        let topics = filter().subscriber(subscriber).to_json()["topics"].clone();
+        let signatures: Vec<B256> = EventKind::ALL.iter().map(|e| e.signature_hash()).collect();
+        assert_eq!(topics, json!([signatures, address_topic(&subscriber)]));
+    }
+
+    #[test]
+    fn selects_given_events_once() {
+        let f = filter()
+            .events([EventKind::BlobAdded, EventKind::BlobDeleted, EventKind::BlobAdded])
+            .subscribers([Address::repeat_byte(1), Address::repeat_byte(2)]);
+        let topics = f.build().topics;
+        assert_eq!(topics[0], [BlobAdded::SIGNATURE_HASH, BlobDeleted::SIGNATURE_HASH]);
+        assert_eq!(topics[1].len(), 2);
+        assert_eq!(topics[1][0].as_slice()[..12], [0; 12]);
+        assert_eq!(topics[1][0].as_slice()[12..], [1; 20]);
+    }
+}
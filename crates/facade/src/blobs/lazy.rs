@@ -0,0 +1,365 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Zero-copy views over raw blobs facade logs.
+//!
+//! Unlike [`Events`](super::Events), a view doesn't decode the log up front. Construction only
+//! checks the event signature, the topic count and the data length, so filtering on the event
+//! kind or the indexed `subscriber` topic is a couple of word comparisons. Data fields are read
+//! on demand and borrow from the log buffers.
+//!
+//! Views don't validate word padding. Use [`LazyEvent::decode`] (or the `Events` decoder with
+//! `validate = true`) if that matters.
+
+use alloy_primitives::{Address, LogData, B256, U256};
+use alloy_sol_types::SolEvent;
+
+use super::{BlobAdded, BlobDeleted, BlobFinalized, BlobPending, Events};
+use crate::abi;
+
+macro_rules! blob_event_view {
+    ($(#[$doc:meta])* $view:ident, $event:ident, $words:expr) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Debug)]
+        pub struct $view<'a> {
+            topics: &'a [B256],
+            data: &'a [u8],
+        }
+
+        impl<'a> $view<'a> {
+            /// Number of static data words in the event.
+            const WORDS: usize = $words;
+
+            /// Wraps a raw log if it carries the event's signature hash and enough data.
+            pub fn new(topics: &'a [B256], data: &'a [u8]) -> Option<Self> {
+                if topics.len() != 2
+                    || topics[0] != <$event as SolEvent>::SIGNATURE_HASH
+                    || data.len() < Self::WORDS * abi::WORD
+                {
+                    return None;
+                }
+                Some(Self { topics, data })
+            }
+
+            /// Wraps a [`LogData`] if it carries the event's signature hash and enough data.
+            pub fn from_log(log: &'a LogData) -> Option<Self> {
+                Self::new(log.topics(), &log.data)
+            }
+
+            /// The raw indexed `subscriber` topic.
+            pub fn subscriber_topic(&self) -> &'a B256 {
+                &self.topics[1]
+            }
+
+            /// The indexed subscriber address.
+            pub fn subscriber(&self) -> Address {
+                abi::word_address(self.subscriber_topic())
+            }
+
+            /// Returns true if the indexed subscriber is `address`, without decoding the topic.
+            pub fn is_subscriber(&self, address: &Address) -> bool {
+                *self.subscriber_topic() == address.into_word()
+            }
+
+            /// The blob blake3 hash.
+            pub fn hash(&self) -> &'a B256 {
+                self.b256(0)
+            }
+
+            fn b256(&self, index: usize) -> &'a B256 {
+                // Length was checked in `new`.
+                abi::word_b256(self.data, index).unwrap()
+            }
+
+            fn u256(&self, index: usize) -> U256 {
+                // Length was checked in `new`.
+                abi::word_u256(self.data, index).unwrap()
+            }
+        }
+    };
+}
+
+blob_event_view!(
+    /// A lazily decoded [`BlobAdded`] log.
+    BlobAddedView,
+    BlobAdded,
+    4
+);
+
+impl BlobAddedView<'_> {
+    /// The blob size.
+    pub fn size(&self) -> U256 {
+        self.u256(1)
+    }
+
+    /// The blob expiry epoch.
+    pub fn expiry(&self) -> U256 {
+        self.u256(2)
+    }
+
+    /// Network capacity bytes used.
+    pub fn bytes_used(&self) -> U256 {
+        self.u256(3)
+    }
+
+    /// Decodes all fields into an owned [`BlobAdded`].
+    pub fn decode(&self) -> BlobAdded {
+        BlobAdded {
+            subscriber: self.subscriber(),
+            hash: *self.hash(),
+            size: self.size(),
+            expiry: self.expiry(),
+            bytesUsed: self.bytes_used(),
+        }
+    }
+}
+
+blob_event_view!(
+    /// A lazily decoded [`BlobPending`] log.
+    BlobPendingView,
+    BlobPending,
+    2
+);
+
+impl<'a> BlobPendingView<'a> {
+    /// The Iroh node ID (public key) providing the blob.
+    pub fn source_id(&self) -> &'a B256 {
+        self.b256(1)
+    }
+
+    /// Decodes all fields into an owned [`BlobPending`].
+    pub fn decode(&self) -> BlobPending {
+        BlobPending {
+            subscriber: self.subscriber(),
+            hash: *self.hash(),
+            sourceId: *self.source_id(),
+        }
+    }
+}
+
+blob_event_view!(
+    /// A lazily decoded [`BlobFinalized`] log.
+    BlobFinalizedView,
+    BlobFinalized,
+    2
+);
+
+impl BlobFinalizedView<'_> {
+    /// Whether the blob was successfully resolved by the network.
+    pub fn resolved(&self) -> bool {
+        // Length was checked in `new`.
+        abi::word_bool(self.data, 1).unwrap()
+    }
+
+    /// Decodes all fields into an owned [`BlobFinalized`].
+    pub fn decode(&self) -> BlobFinalized {
+        BlobFinalized {
+            subscriber: self.subscriber(),
+            hash: *self.hash(),
+            resolved: self.resolved(),
+        }
+    }
+}
+
+blob_event_view!(
+    /// A lazily decoded [`BlobDeleted`] log.
+    BlobDeletedView,
+    BlobDeleted,
+    3
+);
+
+impl BlobDeletedView<'_> {
+    /// The blob size.
+    pub fn size(&self) -> U256 {
+        self.u256(1)
+    }
+
+    /// Network capacity bytes released.
+    pub fn bytes_released(&self) -> U256 {
+        self.u256(2)
+    }
+
+    /// Decodes all fields into an owned [`BlobDeleted`].
+    pub fn decode(&self) -> BlobDeleted {
+        BlobDeleted {
+            subscriber: self.subscriber(),
+            hash: *self.hash(),
+            size: self.size(),
+            bytesReleased: self.bytes_released(),
+        }
+    }
+}
+
+/// A lazily decoded blobs facade log.
+#[derive(Clone, Copy, Debug)]
+pub enum LazyEvent<'a> {
+    BlobAdded(BlobAddedView<'a>),
+    BlobPending(BlobPendingView<'a>),
+    BlobFinalized(BlobFinalizedView<'a>),
+    BlobDeleted(BlobDeletedView<'a>),
+}
+
+impl<'a> LazyEvent<'a> {
+    /// Wraps a raw log if it is one of the blobs facade events.
+    pub fn new(topics: &'a [B256], data: &'a [u8]) -> Option<Self> {
+        match *topics.first()? {
+            <BlobAdded as SolEvent>::SIGNATURE_HASH => {
+                BlobAddedView::new(topics, data).map(Self::BlobAdded)
+            }
+            <BlobPending as SolEvent>::SIGNATURE_HASH => {
+                BlobPendingView::new(topics, data).map(Self::BlobPending)
+            }
+            <BlobFinalized as SolEvent>::SIGNATURE_HASH => {
+                BlobFinalizedView::new(topics, data).map(Self::BlobFinalized)
+            }
+            <BlobDeleted as SolEvent>::SIGNATURE_HASH => {
+                BlobDeletedView::new(topics, data).map(Self::BlobDeleted)
+            }
+            _ => None,
+        }
+    }
+
+    /// Wraps a [`LogData`] if it is one of the blobs facade events.
+    pub fn from_log(log: &'a LogData) -> Option<Self> {
+        Self::new(log.topics(), &log.data)
+    }
+
+    /// The indexed subscriber address.
+    pub fn subscriber(&self) -> Address {
+        match self {
+            Self::BlobAdded(v) => v.subscriber(),
+            Self::BlobPending(v) => v.subscriber(),
+            Self::BlobFinalized(v) => v.subscriber(),
+            Self::BlobDeleted(v) => v.subscriber(),
+        }
+    }
+
+    /// Returns true if the indexed subscriber is `address`, without decoding the topic.
+    pub fn is_subscriber(&self, address: &Address) -> bool {
+        match self {
+            Self::BlobAdded(v) => v.is_subscriber(address),
+            Self::BlobPending(v) => v.is_subscriber(address),
+            Self::BlobFinalized(v) => v.is_subscriber(address),
+            Self::BlobDeleted(v) => v.is_subscriber(address),
+        }
+    }
+
+    /// The blob blake3 hash.
+    pub fn hash(&self) -> &'a B256 {
+        match self {
+            Self::BlobAdded(v) => v.hash(),
+            Self::BlobPending(v) => v.hash(),
+            Self::BlobFinalized(v) => v.hash(),
+            Self::BlobDeleted(v) => v.hash(),
+        }
+    }
+
+    /// Decodes all fields into an owned [`Events`] value.
+    pub fn decode(&self) -> Events {
+        match self {
+            Self::BlobAdded(v) => Events::BlobAdded(v.decode()),
+            Self::BlobPending(v) => Events::BlobPending(v.decode()),
+            Self::BlobFinalized(v) => Events::BlobFinalized(v.decode()),
+            Self::BlobDeleted(v) => Events::BlobDeleted(v.decode()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{IntoLogData, Log};
+    use alloy_sol_types::SolEventInterface;
+
+    use super::*;
+
+    const SUBSCRIBER: Address = Address::repeat_byte(0x11);
+    const HASH: B256 = B256::repeat_byte(0x22);
+
+    fn logs() -> Vec<LogData> {
+        vec![
+            BlobAdded {
+                subscriber: SUBSCRIBER,
+                hash: HASH,
+                size: U256::from(1),
+                expiry: U256::from(2),
+                bytesUsed: U256::MAX,
+            }
+            .encode_log_data(),
+            BlobPending {
+                subscriber: SUBSCRIBER,
+                hash: HASH,
+                sourceId: B256::repeat_byte(0x33),
+            }
+            .encode_log_data(),
+            BlobFinalized {
+                subscriber: SUBSCRIBER,
+                hash: HASH,
+                resolved: true,
+            }
+            .encode_log_data(),
+            BlobDeleted {
+                subscriber: SUBSCRIBER,
+                hash: HASH,
+                size: U256::from(4),
+                bytesReleased: U256::from(5),
+            }
+            .encode_log_data(),
+        ]
+    }
+
+    /// Decodes with the eager decoder and re-encodes, to compare all fields.
+    fn eager(log: &LogData) -> LogData {
+        let log = Log {
+            address: Address::ZERO,
+            data: log.clone(),
+        };
+        Events::decode_log(&log, true).unwrap().data.into_log_data()
+    }
+
+    #[test]
+    fn decodes_like_events() {
+        for log in logs() {
+            let lazy = LazyEvent::from_log(&log).unwrap();
+            assert_eq!(lazy.decode().into_log_data(), eager(&log));
+            assert_eq!(lazy.subscriber(), SUBSCRIBER);
+            assert!(lazy.is_subscriber(&SUBSCRIBER));
+            assert!(!lazy.is_subscriber(&Address::ZERO));
+            assert_eq!(lazy.hash(), &HASH);
+        }
+    }
+
+    #[test]
+    fn accessors_match_fields() {
+        let logs = logs();
+        let Events::BlobAdded(added) = Events::decode_raw_log(logs[0].topics(), &logs[0].data, true)
+            .unwrap()
+        else {
+            panic!("expected BlobAdded");
+        };
+        let view = BlobAddedView::from_log(&logs[0]).unwrap();
+        assert_eq!(
+            (view.size(), view.expiry(), view.bytes_used()),
+            (added.size, added.expiry, added.bytesUsed)
+        );
+        let view = BlobPendingView::from_log(&logs[1]).unwrap();
+        assert_eq!(view.source_id(), &B256::repeat_byte(0x33));
+        assert!(BlobFinalizedView::from_log(&logs[2]).unwrap().resolved());
+        let view = BlobDeletedView::from_log(&logs[3]).unwrap();
+        assert_eq!((view.size(), view.bytes_released()), (U256::from(4), U256::from(5)));
+    }
+
+    #[test]
+    fn rejects_other_logs() {
+        let logs = logs();
+        // Each view only accepts its own event.
+        assert!(BlobAddedView::from_log(&logs[1]).is_none());
+        assert!(BlobDeletedView::from_log(&logs[0]).is_none());
+
+        let topics = logs[0].topics();
+        let data = &logs[0].data;
+        assert!(LazyEvent::new(topics, &data[..data.len() - 1]).is_none());
+        assert!(LazyEvent::new(&topics[..1], data).is_none());
+        assert!(LazyEvent::new(&[], data).is_none());
+        assert!(LazyEvent::new(&[B256::ZERO, topics[1]], data).is_none());
+    }
+}
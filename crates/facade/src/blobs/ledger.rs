@@ -0,0 +1,277 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use alloy_primitives::{Address, B256, U256};
+
+use super::{BlobAdded, BlobDeleted, BlobFinalized, BlobPending, Events, Status, SubnetStats};
+
+/// Storage used by one subscriber.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// Sum of `BlobAdded.bytesUsed` minus sum of `BlobDeleted.bytesReleased`.
+    pub bytes_used: U256,
+    /// Number of blobs the subscriber is subscribed to.
+    pub blobs: u64,
+}
+
+#[derive(Clone, Debug)]
+struct BlobEntry {
+    size: U256,
+    status: Status,
+    subscribers: HashSet<Address>,
+}
+
+/// A mismatch between the [`Ledger`] and `SubnetStats`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Discrepancy {
+    /// The `SubnetStats` field name, e.g. `capacityUsed`.
+    pub field: &'static str,
+    pub ledger: U256,
+    pub stats: U256,
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: ledger has {}, stats report {}",
+            self.field, self.ledger, self.stats
+        )
+    }
+}
+
+/// Per-subscriber storage usage, folded from blobs facade events.
+///
+/// Events must be applied in chain order. The ledger only knows about blobs added after the
+/// first applied event, so reconciling a ledger that did not start at the facade's deployment
+/// will report the difference as discrepancies. Unlike [`Lifecycle`](super::Lifecycle), the
+/// ledger never rejects events: it is a billing view, not a validator.
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    subscribers: HashMap<Address, Usage>,
+    blobs: HashMap<B256, BlobEntry>,
+}
+
+impl Ledger {
+    /// Returns the usage of `subscriber`, if it ever added a blob.
+    pub fn usage(&self, subscriber: Address) -> Option<&Usage> {
+        self.subscribers.get(&subscriber)
+    }
+
+    /// Iterates over all subscribers and their usage.
+    pub fn subscribers(&self) -> impl Iterator<Item = (Address, &Usage)> {
+        self.subscribers.iter().map(|(sub, usage)| (*sub, usage))
+    }
+
+    /// Total bytes used by all subscribers, comparable to `SubnetStats.capacityUsed`.
+    pub fn bytes_used(&self) -> U256 {
+        self.subscribers
+            .values()
+            .fold(U256::ZERO, |acc, u| acc.saturating_add(u.bytes_used))
+    }
+
+    /// Number of distinct blobs with at least one subscriber, comparable to
+    /// `SubnetStats.numBlobs`.
+    pub fn num_blobs(&self) -> u64 {
+        self.blobs.len() as u64
+    }
+
+    /// Total size of blobs not yet picked up for resolution, comparable to
+    /// `SubnetStats.bytesAdded`.
+    pub fn bytes_added(&self) -> U256 {
+        self.blobs
+            .values()
+            .filter(|b| b.status == Status::Added)
+            .fold(U256::ZERO, |acc, b| acc.saturating_add(b.size))
+    }
+
+    /// Applies a blobs facade event.
+    pub fn apply(&mut self, event: &Events) {
+        match event {
+            Events::BlobAdded(e) => self.apply_added(e),
+            Events::BlobPending(e) => self.apply_pending(e),
+            Events::BlobFinalized(e) => self.apply_finalized(e),
+            Events::BlobDeleted(e) => self.apply_deleted(e),
+        }
+    }
+
+    pub fn apply_added(&mut self, event: &BlobAdded) {
+        let usage = self.subscribers.entry(event.subscriber).or_default();
+        usage.bytes_used = usage.bytes_used.saturating_add(event.bytesUsed);
+        let blob = self.blobs.entry(event.hash).or_insert_with(|| BlobEntry {
+            size: event.size,
+            status: Status::Added,
+            subscribers: HashSet::new(),
+        });
+        // The actor retries failed blobs when they are added again.
+        if blob.status == Status::Failed {
+            blob.status = Status::Added;
+        }
+        if blob.subscribers.insert(event.subscriber) {
+            usage.blobs += 1;
+        }
+    }
+
+    pub fn apply_pending(&mut self, event: &BlobPending) {
+        if let Some(blob) = self.blobs.get_mut(&event.hash) {
+            blob.status = Status::Pending;
+        }
+    }
+
+    pub fn apply_finalized(&mut self, event: &BlobFinalized) {
+        if let Some(blob) = self.blobs.get_mut(&event.hash) {
+            blob.status = if event.resolved {
+                Status::Resolved
+            } else {
+                Status::Failed
+            };
+        }
+    }
+
+    /// Releases the subscriber's bytes. Deletions by subscribers that never added a blob, i.e.
+    /// of blobs added before the first applied event, are ignored.
+    pub fn apply_deleted(&mut self, event: &BlobDeleted) {
+        let Some(usage) = self.subscribers.get_mut(&event.subscriber) else {
+            return;
+        };
+        usage.bytes_used = usage.bytes_used.saturating_sub(event.bytesReleased);
+        if let Some(blob) = self.blobs.get_mut(&event.hash) {
+            if blob.subscribers.remove(&event.subscriber) {
+                usage.blobs = usage.blobs.saturating_sub(1);
+            }
+            if blob.subscribers.is_empty() {
+                self.blobs.remove(&event.hash);
+            }
+        }
+    }
+
+    /// Compares the ledger against `getStats()` and returns all mismatching fields.
+    pub fn reconcile(&self, stats: &SubnetStats) -> Vec<Discrepancy> {
+        let checks = [
+            ("capacityUsed", self.bytes_used(), U256::from(stats.capacityUsed)),
+            ("numBlobs", U256::from(self.num_blobs()), U256::from(stats.numBlobs)),
+            ("bytesAdded", self.bytes_added(), U256::from(stats.bytesAdded)),
+        ];
+        checks
+            .into_iter()
+            .filter(|(_, ledger, stats)| ledger != stats)
+            .map(|(field, ledger, stats)| Discrepancy {
+                field,
+                ledger,
+                stats,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: Address = Address::repeat_byte(1);
+    const BOB: Address = Address::repeat_byte(2);
+
+    fn added(subscriber: Address, hash: u8, size: u64) -> Events {
+        Events::BlobAdded(BlobAdded {
+            subscriber,
+            hash: B256::repeat_byte(hash),
+            size: U256::from(size),
+            expiry: U256::from(100),
+            bytesUsed: U256::from(size),
+        })
+    }
+
+    fn deleted(subscriber: Address, hash: u8, size: u64) -> Events {
+        Events::BlobDeleted(BlobDeleted {
+            subscriber,
+            hash: B256::repeat_byte(hash),
+            size: U256::from(size),
+            bytesReleased: U256::from(size),
+        })
+    }
+
+    fn stats(capacity_used: u64, num_blobs: u64, bytes_added: u64) -> SubnetStats {
+        SubnetStats {
+            balance: U256::ZERO,
+            capacityFree: 1 << 30,
+            capacityUsed: capacity_used,
+            creditSold: U256::ZERO,
+            creditCommitted: U256::ZERO,
+            creditDebited: U256::ZERO,
+            tokenCreditRate: U256::from(1),
+            numAccounts: 2,
+            numBlobs: num_blobs,
+            numAdded: 0,
+            bytesAdded: bytes_added,
+            numResolving: 0,
+            bytesResolving: 0,
+        }
+    }
+
+    #[test]
+    fn usage_per_subscriber() {
+        let mut ledger = Ledger::default();
+        for event in [added(ALICE, 1, 10), added(ALICE, 2, 20), added(BOB, 1, 10)] {
+            ledger.apply(&event);
+        }
+        assert_eq!(ledger.usage(ALICE).unwrap().bytes_used, U256::from(30));
+        assert_eq!(ledger.usage(ALICE).unwrap().blobs, 2);
+        assert_eq!(ledger.num_blobs(), 2);
+
+        ledger.apply(&deleted(ALICE, 1, 10));
+        assert_eq!(
+            ledger.usage(ALICE),
+            Some(&Usage {
+                bytes_used: U256::from(20),
+                blobs: 1,
+            })
+        );
+        // Bob still subscribes to blob 1.
+        assert_eq!(ledger.num_blobs(), 2);
+        assert_eq!(ledger.bytes_used(), U256::from(30));
+    }
+
+    #[test]
+    fn deletion_by_unknown_subscriber_is_ignored() {
+        let mut ledger = Ledger::default();
+        ledger.apply(&deleted(ALICE, 1, 10));
+        assert_eq!(ledger.usage(ALICE), None);
+        assert_eq!(ledger.subscribers().count(), 0);
+    }
+
+    #[test]
+    fn reconciles_with_stats() {
+        let mut ledger = Ledger::default();
+        for event in [added(ALICE, 1, 10), added(BOB, 2, 20), added(BOB, 1, 10)] {
+            ledger.apply(&event);
+        }
+        ledger.apply(&Events::BlobPending(BlobPending {
+            subscriber: BOB,
+            hash: B256::repeat_byte(2),
+            sourceId: B256::ZERO,
+        }));
+        // Blob 1 is still added, blob 2 is resolving.
+        assert_eq!(ledger.reconcile(&stats(40, 2, 10)), []);
+
+        let discrepancies = ledger.reconcile(&stats(30, 2, 30));
+        assert_eq!(
+            discrepancies,
+            [
+                Discrepancy {
+                    field: "capacityUsed",
+                    ledger: U256::from(40),
+                    stats: U256::from(30),
+                },
+                Discrepancy {
+                    field: "bytesAdded",
+                    ledger: U256::from(10),
+                    stats: U256::from(30),
+                },
+            ]
+        );
+        assert_eq!(discrepancies[0].to_string(), "capacityUsed: ledger has 40, stats report 30");
+    }
+}
@@ -0,0 +1,259 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use alloy_primitives::{Address, B256};
+use anyhow::bail;
+
+use super::{addBlobCall, overwriteBlobCall};
+use crate::config::SubnetConfig;
+
+/// Builds `addBlob` and `overwriteBlob` calls, validating them against the subnet config.
+///
+/// Catches the mistakes that would otherwise only fail on chain: a zero size, a size above the
+/// subnet capacity, a missing source or blob hash, and a TTL below `blobMinTtl`. If no TTL is
+/// set, `blobDefaultTtl` is used. Hashes and node IDs accept anything that converts into
+/// `B256`, including [`BlobHash`](crate::hash::BlobHash) and [`NodeId`](crate::hash::NodeId).
+#[derive(Clone, Debug)]
+pub struct BlobRequest<'a> {
+    config: &'a SubnetConfig,
+    sponsor: Address,
+    source: B256,
+    blob_hash: B256,
+    metadata_hash: B256,
+    subscription_id: String,
+    size: u64,
+    ttl: Option<u64>,
+}
+
+impl<'a> BlobRequest<'a> {
+    pub fn new(config: &'a SubnetConfig) -> Self {
+        Self {
+            config,
+            sponsor: Address::ZERO,
+            source: B256::ZERO,
+            blob_hash: B256::ZERO,
+            metadata_hash: B256::ZERO,
+            subscription_id: String::new(),
+            size: 0,
+            ttl: None,
+        }
+    }
+
+    /// Sets the account whose credit pays for the blob. Defaults to the caller.
+    pub fn sponsor(mut self, sponsor: Address) -> Self {
+        self.sponsor = sponsor;
+        self
+    }
+
+    /// Sets the node the blob is resolved from.
+    pub fn source(mut self, source: impl Into<B256>) -> Self {
+        self.source = source.into();
+        self
+    }
+
+    pub fn blob_hash(mut self, hash: impl Into<B256>) -> Self {
+        self.blob_hash = hash.into();
+        self
+    }
+
+    /// Sets the hash of the blob's metadata. Defaults to zero, i.e. no metadata.
+    pub fn metadata_hash(mut self, hash: impl Into<B256>) -> Self {
+        self.metadata_hash = hash.into();
+        self
+    }
+
+    /// Sets the subscription ID. Defaults to the empty string, i.e. the default subscription.
+    pub fn subscription_id(mut self, id: impl Into<String>) -> Self {
+        self.subscription_id = id.into();
+        self
+    }
+
+    /// Sets the blob size in bytes.
+    pub fn size(mut self, size: u64) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the TTL in epochs. Defaults to `blobDefaultTtl`.
+    pub fn ttl(mut self, ttl: u64) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Validates the request and returns the `addBlob` call.
+    pub fn build(&self) -> anyhow::Result<addBlobCall> {
+        let ttl = self.validate()?;
+        Ok(addBlobCall {
+            sponsor: self.sponsor,
+            source: self.source,
+            blobHash: self.blob_hash,
+            metadataHash: self.metadata_hash,
+            subscriptionId: self.subscription_id.clone(),
+            size: self.size,
+            ttl,
+        })
+    }
+
+    /// Validates the request and returns an `overwriteBlob` call that replaces `old_hash`.
+    pub fn build_overwrite(
+        &self,
+        old_hash: impl Into<B256>,
+    ) -> anyhow::Result<overwriteBlobCall> {
+        let old_hash = old_hash.into();
+        if old_hash.is_zero() {
+            bail!("invalid overwriteBlob request: old hash is zero");
+        }
+        let ttl = self.validate()?;
+        Ok(overwriteBlobCall {
+            oldHash: old_hash,
+            sponsor: self.sponsor,
+            source: self.source,
+            blobHash: self.blob_hash,
+            metadataHash: self.metadata_hash,
+            subscriptionId: self.subscription_id.clone(),
+            size: self.size,
+            ttl,
+        })
+    }
+
+    /// Returns the effective TTL, or an error listing every problem with the request.
+    fn validate(&self) -> anyhow::Result<u64> {
+        let ttl = self.ttl.unwrap_or(self.config.blob_default_ttl);
+        let mut problems = Vec::new();
+        if self.source.is_zero() {
+            problems.push("source is not set".to_string());
+        }
+        if self.blob_hash.is_zero() {
+            problems.push("blob hash is not set".to_string());
+        }
+        if self.size == 0 {
+            problems.push("size is zero".to_string());
+        } else if self.size > self.config.blob_capacity {
+            problems.push(format!(
+                "size {} exceeds the subnet capacity of {} bytes",
+                self.size, self.config.blob_capacity
+            ));
+        }
+        if ttl < self.config.blob_min_ttl {
+            problems.push(format!(
+                "ttl {} is below the minimum of {} epochs",
+                ttl, self.config.blob_min_ttl
+            ));
+        }
+        if !problems.is_empty() {
+            bail!("invalid blob request: {}", problems.join("; "));
+        }
+        Ok(ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::U256;
+
+    use super::*;
+    use crate::config::ConfigSet;
+    use crate::hash::{BlobHash, NodeId};
+
+    fn config() -> SubnetConfig {
+        SubnetConfig::try_from(ConfigSet {
+            blobCapacity: U256::from(1000),
+            tokenCreditRate: U256::from(1),
+            blobCreditDebitInterval: U256::from(10),
+            blobMinTtl: U256::from(3600),
+            blobDefaultTtl: U256::from(86_400),
+            blobDeleteBatchSize: U256::from(100),
+            accountDebitBatchSize: U256::from(100),
+        })
+        .unwrap()
+    }
+
+    fn valid(config: &SubnetConfig) -> BlobRequest<'_> {
+        BlobRequest::new(config)
+            .source(NodeId::new(B256::repeat_byte(1)))
+            .blob_hash(BlobHash::new(B256::repeat_byte(2)))
+            .size(10)
+    }
+
+    fn problems(request: &BlobRequest) -> String {
+        request.build().err().unwrap().to_string()
+    }
+
+    #[test]
+    fn builds_with_default_ttl() {
+        let config = config();
+        let call = valid(&config)
+            .sponsor(Address::repeat_byte(3))
+            .metadata_hash(B256::repeat_byte(4))
+            .subscription_id("backup")
+            .build()
+            .unwrap();
+        assert_eq!((call.source, call.blobHash), (B256::repeat_byte(1), B256::repeat_byte(2)));
+        assert_eq!(call.sponsor, Address::repeat_byte(3));
+        assert_eq!(call.metadataHash, B256::repeat_byte(4));
+        assert_eq!((call.subscriptionId.as_str(), call.size, call.ttl), ("backup", 10, 86_400));
+        assert_eq!(valid(&config).ttl(7200).build().unwrap().ttl, 7200);
+    }
+
+    #[test]
+    fn rejects_unset_fields_and_zero_size() {
+        let config = config();
+        assert_eq!(
+            problems(&BlobRequest::new(&config)),
+            "invalid blob request: source is not set; blob hash is not set; size is zero"
+        );
+        assert_eq!(
+            problems(&valid(&config).size(0)),
+            "invalid blob request: size is zero"
+        );
+        assert_eq!(
+            problems(&valid(&config).source(B256::ZERO)),
+            "invalid blob request: source is not set"
+        );
+    }
+
+    #[test]
+    fn checks_ttl_against_minimum() {
+        let config = config();
+        assert!(valid(&config).ttl(3600).build().is_ok());
+        assert_eq!(
+            problems(&valid(&config).ttl(3599)),
+            "invalid blob request: ttl 3599 is below the minimum of 3600 epochs"
+        );
+        // The default TTL is checked too.
+        let config = SubnetConfig {
+            blob_default_ttl: 60,
+            ..config
+        };
+        assert_eq!(
+            problems(&valid(&config)),
+            "invalid blob request: ttl 60 is below the minimum of 3600 epochs"
+        );
+    }
+
+    #[test]
+    fn checks_capacity() {
+        let config = config();
+        assert!(valid(&config).size(1000).build().is_ok());
+        assert_eq!(
+            problems(&valid(&config).size(1001).ttl(1)),
+            "invalid blob request: size 1001 exceeds the subnet capacity of 1000 bytes; \
+             ttl 1 is below the minimum of 3600 epochs"
+        );
+    }
+
+    #[test]
+    fn builds_overwrite() {
+        let config = config();
+        let call = valid(&config).build_overwrite(B256::repeat_byte(9)).unwrap();
+        assert_eq!((call.oldHash, call.blobHash), (B256::repeat_byte(9), B256::repeat_byte(2)));
+        assert_eq!((call.size, call.ttl), (10, 86_400));
+        assert_eq!(
+            valid(&config).build_overwrite(B256::ZERO).err().unwrap().to_string(),
+            "invalid overwriteBlob request: old hash is zero"
+        );
+        // The new blob is validated too.
+        let err = valid(&config).size(0).build_overwrite(B256::repeat_byte(9)).err().unwrap();
+        assert_eq!(err.to_string(), "invalid blob request: size is zero");
+    }
+}
@@ -0,0 +1,422 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::fmt;
+use std::time::Duration;
+
+use alloy_primitives::{I256, U256};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+use super::SubnetStats;
+
+/// Metrics derived from one `getStats()` snapshot.
+///
+/// Ratios are `None` when their denominator is zero. Serializes to JSON with 256-bit values as
+/// decimal strings.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsMetrics {
+    /// `capacityUsed + capacityFree`, in bytes.
+    pub capacity_total: u64,
+    pub capacity_used: u64,
+    pub capacity_free: u64,
+    /// `capacityUsed / capacityTotal`, between 0 and 1.
+    pub capacity_utilization: Option<f64>,
+    #[serde(serialize_with = "display")]
+    pub credit_sold: U256,
+    #[serde(serialize_with = "display")]
+    pub credit_committed: U256,
+    #[serde(serialize_with = "display")]
+    pub credit_debited: U256,
+    /// `creditCommitted / creditSold`.
+    pub credit_committed_ratio: Option<f64>,
+    /// `capacityUsed / numBlobs`, in bytes.
+    pub average_blob_size: Option<f64>,
+    /// Blobs added but not yet resolving, plus blobs resolving.
+    pub backlog_blobs: u64,
+    /// Bytes of the backlog blobs.
+    pub backlog_bytes: u64,
+    pub num_accounts: u64,
+    pub num_blobs: u64,
+}
+
+impl From<&SubnetStats> for StatsMetrics {
+    fn from(stats: &SubnetStats) -> Self {
+        let capacity_total = stats.capacityUsed.saturating_add(stats.capacityFree);
+        Self {
+            capacity_total,
+            capacity_used: stats.capacityUsed,
+            capacity_free: stats.capacityFree,
+            capacity_utilization: ratio(stats.capacityUsed as f64, capacity_total as f64),
+            credit_sold: stats.creditSold,
+            credit_committed: stats.creditCommitted,
+            credit_debited: stats.creditDebited,
+            credit_committed_ratio: ratio(
+                f64::from(stats.creditCommitted),
+                f64::from(stats.creditSold),
+            ),
+            average_blob_size: ratio(stats.capacityUsed as f64, stats.numBlobs as f64),
+            backlog_blobs: stats.numAdded.saturating_add(stats.numResolving),
+            backlog_bytes: stats.bytesAdded.saturating_add(stats.bytesResolving),
+            num_accounts: stats.numAccounts,
+            num_blobs: stats.numBlobs,
+        }
+    }
+}
+
+impl StatsMetrics {
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).expect("metrics serialize to JSON")
+    }
+}
+
+impl fmt::Display for StatsMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "capacity:  {} of {} used ({}), {} free",
+            Bytes(self.capacity_used as f64),
+            Bytes(self.capacity_total as f64),
+            Percent(self.capacity_utilization),
+            Bytes(self.capacity_free as f64)
+        )?;
+        writeln!(
+            f,
+            "credit:    {} sold, {} committed ({}), {} debited",
+            self.credit_sold,
+            self.credit_committed,
+            Percent(self.credit_committed_ratio),
+            self.credit_debited
+        )?;
+        writeln!(
+            f,
+            "blobs:     {} across {} accounts, {} on average",
+            self.num_blobs,
+            self.num_accounts,
+            self.average_blob_size.map_or("-".to_string(), |s| Bytes(s).to_string())
+        )?;
+        write!(
+            f,
+            "backlog:   {} blobs, {}",
+            self.backlog_blobs,
+            Bytes(self.backlog_bytes as f64)
+        )
+    }
+}
+
+/// The change between two `getStats()` snapshots taken `elapsed` apart.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsDelta {
+    pub elapsed_secs: f64,
+    pub capacity_used: i128,
+    pub num_accounts: i128,
+    pub num_blobs: i128,
+    pub backlog_blobs: i128,
+    pub backlog_bytes: i128,
+    #[serde(serialize_with = "display")]
+    pub balance: I256,
+    #[serde(serialize_with = "display")]
+    pub credit_sold: I256,
+    #[serde(serialize_with = "display")]
+    pub credit_committed: I256,
+    #[serde(serialize_with = "display")]
+    pub credit_debited: I256,
+    pub rates: StatsRates,
+}
+
+/// Per-second rates of a [`StatsDelta`]. All zero if no time elapsed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsRates {
+    /// Bytes of capacity used per second.
+    pub capacity_used: f64,
+    pub num_blobs: f64,
+    pub backlog_bytes: f64,
+    /// Atto credits sold per second.
+    pub credit_sold: f64,
+    /// Atto credits debited per second.
+    pub credit_debited: f64,
+}
+
+impl StatsDelta {
+    pub fn between(earlier: &SubnetStats, later: &SubnetStats, elapsed: Duration) -> Self {
+        let (a, b) = (StatsMetrics::from(earlier), StatsMetrics::from(later));
+        let elapsed_secs = elapsed.as_secs_f64();
+        let mut delta = Self {
+            elapsed_secs,
+            capacity_used: diff(a.capacity_used, b.capacity_used),
+            num_accounts: diff(a.num_accounts, b.num_accounts),
+            num_blobs: diff(a.num_blobs, b.num_blobs),
+            backlog_blobs: diff(a.backlog_blobs, b.backlog_blobs),
+            backlog_bytes: diff(a.backlog_bytes, b.backlog_bytes),
+            balance: diff_u256(earlier.balance, later.balance),
+            credit_sold: diff_u256(a.credit_sold, b.credit_sold),
+            credit_committed: diff_u256(a.credit_committed, b.credit_committed),
+            credit_debited: diff_u256(a.credit_debited, b.credit_debited),
+            rates: StatsRates::default(),
+        };
+        if elapsed_secs > 0.0 {
+            delta.rates = StatsRates {
+                capacity_used: delta.capacity_used as f64 / elapsed_secs,
+                num_blobs: delta.num_blobs as f64 / elapsed_secs,
+                backlog_bytes: delta.backlog_bytes as f64 / elapsed_secs,
+                credit_sold: signed_f64(delta.credit_sold) / elapsed_secs,
+                credit_debited: signed_f64(delta.credit_debited) / elapsed_secs,
+            };
+        }
+        delta
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).expect("stats delta serializes to JSON")
+    }
+}
+
+impl fmt::Display for StatsDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "over {:.0}s:", self.elapsed_secs)?;
+        writeln!(
+            f,
+            "capacity:  {:+} bytes ({}/s)",
+            self.capacity_used,
+            Bytes(self.rates.capacity_used)
+        )?;
+        writeln!(
+            f,
+            "blobs:     {:+} ({:.3}/s), accounts {:+}",
+            self.num_blobs, self.rates.num_blobs, self.num_accounts
+        )?;
+        writeln!(
+            f,
+            "backlog:   {:+} blobs, {:+} bytes ({}/s)",
+            self.backlog_blobs,
+            self.backlog_bytes,
+            Bytes(self.rates.backlog_bytes)
+        )?;
+        write!(
+            f,
+            "credit:    {} sold ({:.0}/s), {} debited ({:.0}/s), {} committed",
+            self.credit_sold,
+            self.rates.credit_sold,
+            self.credit_debited,
+            self.rates.credit_debited,
+            self.credit_committed
+        )
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 GiB`.
+struct Bytes(f64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+        let mut value = self.0;
+        let mut unit = 0;
+        while value.abs() >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            write!(f, "{:.0} {}", value, UNITS[unit])
+        } else {
+            write!(f, "{:.1} {}", value, UNITS[unit])
+        }
+    }
+}
+
+struct Percent(Option<f64>);
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(r) => write!(f, "{:.1}%", r * 100.0),
+            None => f.write_str("-"),
+        }
+    }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> Option<f64> {
+    (denominator != 0.0).then(|| numerator / denominator)
+}
+
+fn diff(earlier: u64, later: u64) -> i128 {
+    i128::from(later) - i128::from(earlier)
+}
+
+/// Differences that don't fit in an `I256` saturate at `I256::MAX` or `I256::MIN`.
+fn diff_u256(earlier: U256, later: U256) -> I256 {
+    match later.checked_sub(earlier) {
+        Some(increase) => I256::try_from(increase).unwrap_or(I256::MAX),
+        None => I256::try_from(earlier - later).map_or(I256::MIN, |decrease| -decrease),
+    }
+}
+
+fn signed_f64(value: I256) -> f64 {
+    let magnitude = f64::from(value.unsigned_abs());
+    if value.is_negative() {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+fn display<T: fmt::Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(balance: U256, credit_sold: U256, num_blobs: u64) -> SubnetStats {
+        SubnetStats {
+            balance,
+            capacityFree: 100,
+            capacityUsed: 0,
+            creditSold: credit_sold,
+            creditCommitted: U256::ZERO,
+            creditDebited: U256::ZERO,
+            tokenCreditRate: U256::from(1),
+            numAccounts: 1,
+            numBlobs: num_blobs,
+            numAdded: 0,
+            bytesAdded: 0,
+            numResolving: 0,
+            bytesResolving: 0,
+        }
+    }
+
+    fn busy_stats() -> SubnetStats {
+        SubnetStats {
+            capacityFree: 2560,
+            capacityUsed: 1536,
+            creditCommitted: U256::from(250),
+            creditDebited: U256::from(100),
+            numAccounts: 2,
+            numAdded: 2,
+            bytesAdded: 1024,
+            numResolving: 1,
+            bytesResolving: 2048,
+            ..stats(U256::ZERO, U256::from(1000), 3)
+        }
+    }
+
+    #[test]
+    fn derives_metrics() {
+        let metrics = StatsMetrics::from(&busy_stats());
+        assert_eq!(metrics.capacity_total, 4096);
+        assert_eq!(metrics.capacity_utilization, Some(0.375));
+        assert_eq!(metrics.credit_committed_ratio, Some(0.25));
+        assert_eq!(metrics.average_blob_size, Some(512.0));
+        assert_eq!((metrics.backlog_blobs, metrics.backlog_bytes), (3, 3072));
+    }
+
+    #[test]
+    fn metrics_without_denominators() {
+        let empty = SubnetStats {
+            capacityFree: 0,
+            ..stats(U256::ZERO, U256::ZERO, 0)
+        };
+        let metrics = StatsMetrics::from(&empty);
+        assert_eq!(metrics.capacity_total, 0);
+        assert_eq!(metrics.capacity_utilization, None);
+        assert_eq!(metrics.credit_committed_ratio, None);
+        assert_eq!(metrics.average_blob_size, None);
+        assert_eq!(
+            metrics.to_string(),
+            "capacity:  0 B of 0 B used (-), 0 B free\n\
+             credit:    0 sold, 0 committed (-), 0 debited\n\
+             blobs:     0 across 1 accounts, - on average\n\
+             backlog:   0 blobs, 0 B"
+        );
+    }
+
+    #[test]
+    fn metrics_saturate() {
+        let full = SubnetStats {
+            capacityFree: u64::MAX,
+            capacityUsed: 1,
+            numAdded: u64::MAX,
+            numResolving: 1,
+            bytesAdded: u64::MAX,
+            bytesResolving: 1,
+            ..stats(U256::ZERO, U256::ZERO, 1)
+        };
+        let metrics = StatsMetrics::from(&full);
+        assert_eq!(metrics.capacity_total, u64::MAX);
+        assert_eq!((metrics.backlog_blobs, metrics.backlog_bytes), (u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn renders_metrics() {
+        let metrics = StatsMetrics::from(&busy_stats());
+        assert_eq!(
+            metrics.to_string(),
+            "capacity:  1.5 KiB of 4.0 KiB used (37.5%), 2.5 KiB free\n\
+             credit:    1000 sold, 250 committed (25.0%), 100 debited\n\
+             blobs:     3 across 2 accounts, 512 B on average\n\
+             backlog:   3 blobs, 3.0 KiB"
+        );
+        assert_eq!(
+            metrics.to_json(),
+            serde_json::json!({
+                "capacityTotal": 4096,
+                "capacityUsed": 1536,
+                "capacityFree": 2560,
+                "capacityUtilization": 0.375,
+                "creditSold": "1000",
+                "creditCommitted": "250",
+                "creditDebited": "100",
+                "creditCommittedRatio": 0.25,
+                "averageBlobSize": 512.0,
+                "backlogBlobs": 3,
+                "backlogBytes": 3072,
+                "numAccounts": 2,
+                "numBlobs": 3,
+            })
+        );
+        // 256-bit values don't lose precision.
+        let rich = StatsMetrics::from(&stats(U256::ZERO, U256::MAX, 0));
+        assert_eq!(rich.to_json()["creditSold"], U256::MAX.to_string());
+        assert_eq!(rich.to_json()["averageBlobSize"], Value::Null);
+    }
+
+    #[test]
+    fn diff_u256_is_signed() {
+        let (one, two) = (U256::from(1), U256::from(2));
+        assert_eq!(diff_u256(one, two), I256::ONE);
+        assert_eq!(diff_u256(two, one), I256::MINUS_ONE);
+        assert_eq!(diff_u256(two, two), I256::ZERO);
+        assert_eq!(diff_u256(U256::ZERO, I256::MAX.into_raw()), I256::MAX);
+        assert_eq!(diff_u256(I256::MAX.into_raw(), U256::ZERO), -I256::MAX);
+        // 2^255 is exactly `I256::MIN` as a decrease.
+        assert_eq!(diff_u256(I256::MIN.into_raw(), U256::ZERO), I256::MIN);
+    }
+
+    #[test]
+    fn diff_u256_saturates() {
+        assert_eq!(diff_u256(U256::ZERO, U256::MAX), I256::MAX);
+        assert_eq!(diff_u256(U256::MAX, U256::ZERO), I256::MIN);
+        assert_eq!(diff_u256(U256::ZERO, I256::MIN.into_raw()), I256::MAX);
+        assert_eq!(diff_u256(U256::from(1), U256::MAX), I256::MAX);
+    }
+
+    #[test]
+    fn delta_between_snapshots() {
+        let earlier = stats(U256::from(500), U256::MAX, 10);
+        let later = stats(U256::from(300), U256::ZERO, 14);
+        let delta = StatsDelta::between(&earlier, &later, Duration::from_secs(2));
+        assert_eq!(delta.num_blobs, 4);
+        assert_eq!(delta.balance, I256::try_from(-200).unwrap());
+        assert_eq!(delta.credit_sold, I256::MIN);
+        assert_eq!(delta.rates.num_blobs, 2.0);
+        assert!(delta.rates.credit_sold < 0.0);
+        assert_eq!(delta.to_json()["balance"], "-200");
+
+        let still = StatsDelta::between(&earlier, &later, Duration::ZERO);
+        assert_eq!(still.rates, StatsRates::default());
+    }
+}
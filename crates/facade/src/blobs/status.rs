@@ -0,0 +1,462 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::HashMap;
+use std::fmt;
+
+use alloy_primitives::{Address, B256, U256};
+use anyhow::{anyhow, bail};
+
+use super::{Blob, BlobAdded, BlobDeleted, BlobFinalized, BlobPending, Events};
+
+/// The status of a blob, mirroring `BlobStatus` in `BlobTypes.sol`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Status {
+    /// Blob was added to the network.
+    Added,
+    /// Blob is pending resolve.
+    Pending,
+    /// Blob was successfully resolved.
+    Resolved,
+    /// Blob resolution failed.
+    Failed,
+}
+
+impl Status {
+    /// Whether the blob has been finalized, successfully or not.
+    pub fn is_finalized(&self) -> bool {
+        matches!(self, Status::Resolved | Status::Failed)
+    }
+}
+
+impl TryFrom<u8> for Status {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Status::Added),
+            1 => Ok(Status::Pending),
+            2 => Ok(Status::Resolved),
+            3 => Ok(Status::Failed),
+            _ => bail!("invalid blob status {}", value),
+        }
+    }
+}
+
+impl From<Status> for u8 {
+    fn from(value: Status) -> Self {
+        value as u8
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Status::Added => "added",
+            Status::Pending => "pending",
+            Status::Resolved => "resolved",
+            Status::Failed => "failed",
+        };
+        f.write_str(s)
+    }
+}
+
+/// What the lifecycle knows about one (subscriber, hash) pair.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlobState {
+    pub status: Status,
+    /// Blob size in bytes, if seen in a `BlobAdded` event.
+    pub size: Option<U256>,
+    /// Subscription expiry epoch, if seen in a `BlobAdded` event.
+    pub expiry: Option<U256>,
+    /// The node the blob is resolved from, if seen in a `BlobPending` event.
+    pub source_id: Option<B256>,
+}
+
+impl BlobState {
+    fn new(status: Status) -> Self {
+        Self {
+            status,
+            size: None,
+            expiry: None,
+            source_id: None,
+        }
+    }
+}
+
+/// The effect of applying an event to a [`Lifecycle`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Transition {
+    pub subscriber: Address,
+    pub hash: B256,
+    /// Status before the event, or `None` if the blob was not tracked.
+    pub from: Option<Status>,
+    /// Status after the event, or `None` if the blob was deleted.
+    pub to: Option<Status>,
+}
+
+/// Tracks the status of blobs per (subscriber, hash) by applying blobs facade events in order.
+///
+/// The legal transitions are:
+///
+/// - `BlobAdded`: untracked → added. On a tracked blob this renews the subscription; a failed
+///   blob is reset to added, since the actor retries it, while other statuses are kept.
+/// - `BlobPending`: added → pending.
+/// - `BlobFinalized`: pending → resolved or failed, depending on `resolved`.
+/// - `BlobDeleted`: any tracked status → untracked.
+///
+/// The status of a blob is shared by its subscribers, and the actor emits `BlobPending` and
+/// `BlobFinalized` once per blob, naming only one of them. So these transitions, and the reset
+/// of a failed blob, apply to every tracked subscriber of the blob, and a new subscriber of a
+/// blob already tracked for another subscriber starts with that blob's status.
+///
+/// Any other event is rejected with an error and leaves the state unchanged. Start from
+/// [`Lifecycle::default`] when replaying from the facade's deployment, or
+/// [`insert_blob`](Lifecycle::insert_blob) the results of `getBlob` to start midway.
+#[derive(Clone, Debug, Default)]
+pub struct Lifecycle {
+    blobs: HashMap<(Address, B256), BlobState>,
+}
+
+impl Lifecycle {
+    /// Returns the status of `hash` for `subscriber`, if tracked.
+    pub fn status(&self, subscriber: Address, hash: B256) -> Option<Status> {
+        self.get(subscriber, hash).map(|s| s.status)
+    }
+
+    /// Returns the state of `hash` for `subscriber`, if tracked.
+    pub fn get(&self, subscriber: Address, hash: B256) -> Option<&BlobState> {
+        self.blobs.get(&(subscriber, hash))
+    }
+
+    /// Iterates over all tracked (subscriber, hash) pairs and their state.
+    pub fn iter(&self) -> impl Iterator<Item = (Address, B256, &BlobState)> {
+        self.blobs.iter().map(|((sub, hash), state)| (*sub, *hash, state))
+    }
+
+    /// Iterates over all tracked pairs with the given status, e.g. to find blobs stuck in
+    /// [`Status::Pending`].
+    pub fn with_status(&self, status: Status) -> impl Iterator<Item = (Address, B256)> + '_ {
+        self.iter()
+            .filter(move |(_, _, state)| state.status == status)
+            .map(|(sub, hash, _)| (sub, hash))
+    }
+
+    pub fn len(&self) -> usize {
+        self.blobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blobs.is_empty()
+    }
+
+    /// Starts tracking a blob with the status reported by `getBlob`, replacing any known state.
+    pub fn insert_blob(
+        &mut self,
+        subscriber: Address,
+        hash: B256,
+        blob: &Blob,
+    ) -> anyhow::Result<()> {
+        let mut state = BlobState::new(Status::try_from(blob.status)?);
+        state.size = Some(U256::from(blob.size));
+        self.blobs.insert((subscriber, hash), state);
+        Ok(())
+    }
+
+    /// Applies a blobs facade event.
+    pub fn apply(&mut self, event: &Events) -> anyhow::Result<Transition> {
+        match event {
+            Events::BlobAdded(e) => self.apply_added(e),
+            Events::BlobPending(e) => self.apply_pending(e),
+            Events::BlobFinalized(e) => self.apply_finalized(e),
+            Events::BlobDeleted(e) => self.apply_deleted(e),
+        }
+    }
+
+    pub fn apply_added(&mut self, event: &BlobAdded) -> anyhow::Result<Transition> {
+        let key = (event.subscriber, event.hash);
+        let from = self.blobs.get(&key).map(|s| s.status);
+        if from.is_none() {
+            let state = self
+                .shared_state(event.hash)
+                .unwrap_or_else(|| BlobState::new(Status::Added));
+            self.blobs.insert(key, state);
+        }
+        if self.blobs[&key].status == Status::Failed {
+            self.update_shared(event.hash, Status::Failed, |state| {
+                state.status = Status::Added;
+                state.source_id = None;
+            });
+        }
+        let state = self.blobs.get_mut(&key).expect("blob was inserted above");
+        state.size = Some(event.size);
+        state.expiry = Some(event.expiry);
+        Ok(transition(key, from, Some(state.status)))
+    }
+
+    pub fn apply_pending(&mut self, event: &BlobPending) -> anyhow::Result<Transition> {
+        let key = (event.subscriber, event.hash);
+        let status = self.tracked(key, "BlobPending")?.status;
+        if status != Status::Added {
+            bail!(illegal(key, status, "BlobPending", "only added blobs can become pending"));
+        }
+        self.update_shared(event.hash, Status::Added, |state| {
+            state.status = Status::Pending;
+            state.source_id = Some(event.sourceId);
+        });
+        Ok(transition(key, Some(Status::Added), Some(Status::Pending)))
+    }
+
+    pub fn apply_finalized(&mut self, event: &BlobFinalized) -> anyhow::Result<Transition> {
+        let key = (event.subscriber, event.hash);
+        let status = self.tracked(key, "BlobFinalized")?.status;
+        if status != Status::Pending {
+            bail!(illegal(key, status, "BlobFinalized", "only pending blobs can be finalized"));
+        }
+        let to = if event.resolved {
+            Status::Resolved
+        } else {
+            Status::Failed
+        };
+        self.update_shared(event.hash, Status::Pending, |state| state.status = to);
+        Ok(transition(key, Some(Status::Pending), Some(to)))
+    }
+
+    pub fn apply_deleted(&mut self, event: &BlobDeleted) -> anyhow::Result<Transition> {
+        let key = (event.subscriber, event.hash);
+        let from = self.tracked(key, "BlobDeleted")?.status;
+        self.blobs.remove(&key);
+        Ok(transition(key, Some(from), None))
+    }
+
+    /// Returns the most advanced state of `hash` among its tracked subscribers, without the
+    /// subscription fields.
+    fn shared_state(&self, hash: B256) -> Option<BlobState> {
+        let state = self
+            .blobs
+            .iter()
+            .filter(|((_, h), _)| *h == hash)
+            .map(|(_, state)| state)
+            .max_by_key(|state| u8::from(state.status))?;
+        let mut shared = BlobState::new(state.status);
+        shared.source_id = state.source_id;
+        Some(shared)
+    }
+
+    /// Updates the state of every tracked subscriber of `hash` whose blob has status `from`.
+    fn update_shared(&mut self, hash: B256, from: Status, update: impl Fn(&mut BlobState)) {
+        self.blobs
+            .iter_mut()
+            .filter(|((_, h), state)| *h == hash && state.status == from)
+            .for_each(|(_, state)| update(state));
+    }
+
+    fn tracked(&mut self, key: (Address, B256), event: &str) -> anyhow::Result<&mut BlobState> {
+        self.blobs.get_mut(&key).ok_or_else(|| {
+            anyhow!(
+                "illegal {} for blob {} of subscriber {}: blob is not tracked; add it first",
+                event,
+                key.1,
+                key.0
+            )
+        })
+    }
+}
+
+fn transition(key: (Address, B256), from: Option<Status>, to: Option<Status>) -> Transition {
+    Transition {
+        subscriber: key.0,
+        hash: key.1,
+        from,
+        to,
+    }
+}
+
+fn illegal(key: (Address, B256), status: Status, event: &str, reason: &str) -> String {
+    format!(
+        "illegal {} for blob {} of subscriber {}: blob is {}, but {}",
+        event, key.1, key.0, status, reason
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: Address = Address::repeat_byte(1);
+    const BOB: Address = Address::repeat_byte(2);
+    const HASH: B256 = B256::repeat_byte(0xaa);
+
+    fn added(subscriber: Address) -> Events {
+        blob_added(subscriber, HASH)
+    }
+
+    fn blob_added(subscriber: Address, hash: B256) -> Events {
+        Events::BlobAdded(BlobAdded {
+            subscriber,
+            hash,
+            size: U256::from(10),
+            expiry: U256::from(100),
+            bytesUsed: U256::from(10),
+        })
+    }
+
+    fn pending(subscriber: Address) -> Events {
+        Events::BlobPending(BlobPending {
+            subscriber,
+            hash: HASH,
+            sourceId: B256::repeat_byte(7),
+        })
+    }
+
+    fn finalized(subscriber: Address, resolved: bool) -> Events {
+        Events::BlobFinalized(BlobFinalized {
+            subscriber,
+            hash: HASH,
+            resolved,
+        })
+    }
+
+    fn deleted(subscriber: Address) -> Events {
+        Events::BlobDeleted(BlobDeleted {
+            subscriber,
+            hash: HASH,
+            size: U256::from(10),
+            bytesReleased: U256::from(10),
+        })
+    }
+
+    type Step = (Option<Status>, Option<Status>);
+
+    fn steps(lifecycle: &mut Lifecycle, events: &[Events]) -> Vec<Step> {
+        events
+            .iter()
+            .map(|e| lifecycle.apply(e).map(|t| (t.from, t.to)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn legal_transitions() {
+        let mut lifecycle = Lifecycle::default();
+        let events = [added(ALICE), pending(ALICE), finalized(ALICE, true), deleted(ALICE)];
+        assert_eq!(
+            steps(&mut lifecycle, &events),
+            [
+                (None, Some(Status::Added)),
+                (Some(Status::Added), Some(Status::Pending)),
+                (Some(Status::Pending), Some(Status::Resolved)),
+                (Some(Status::Resolved), None),
+            ]
+        );
+        assert!(lifecycle.is_empty());
+    }
+
+    #[test]
+    fn failed_blob_is_retried_on_add() {
+        let mut lifecycle = Lifecycle::default();
+        steps(&mut lifecycle, &[added(ALICE), pending(ALICE), finalized(ALICE, false)]);
+        assert_eq!(lifecycle.status(ALICE, HASH), Some(Status::Failed));
+        assert_eq!(lifecycle.with_status(Status::Failed).count(), 1);
+        assert_eq!(
+            steps(&mut lifecycle, &[added(ALICE)]),
+            [(Some(Status::Failed), Some(Status::Added))]
+        );
+        assert_eq!(lifecycle.get(ALICE, HASH).unwrap().source_id, None);
+    }
+
+    #[test]
+    fn renewal_keeps_status() {
+        let mut lifecycle = Lifecycle::default();
+        steps(&mut lifecycle, &[added(ALICE), pending(ALICE)]);
+        assert_eq!(
+            steps(&mut lifecycle, &[added(ALICE)]),
+            [(Some(Status::Pending), Some(Status::Pending))]
+        );
+        assert_eq!(lifecycle.with_status(Status::Pending).collect::<Vec<_>>(), [(ALICE, HASH)]);
+    }
+
+    #[test]
+    fn new_subscriber_shares_blob_status() {
+        let mut lifecycle = Lifecycle::default();
+        steps(&mut lifecycle, &[added(ALICE), pending(ALICE), finalized(ALICE, true)]);
+        assert_eq!(steps(&mut lifecycle, &[added(BOB)]), [(None, Some(Status::Resolved))]);
+        let bob = lifecycle.get(BOB, HASH).unwrap();
+        assert_eq!(bob.source_id, Some(B256::repeat_byte(7)));
+        assert_eq!(bob.expiry, Some(U256::from(100)));
+        // Deleting one subscription leaves the other.
+        steps(&mut lifecycle, &[deleted(ALICE)]);
+        assert_eq!(lifecycle.status(BOB, HASH), Some(Status::Resolved));
+    }
+
+    #[test]
+    fn status_changes_apply_to_all_subscribers() {
+        let mut lifecycle = Lifecycle::default();
+        let events = [added(ALICE), added(BOB), pending(ALICE), finalized(ALICE, true)];
+        assert_eq!(
+            steps(&mut lifecycle, &events),
+            [
+                (None, Some(Status::Added)),
+                (None, Some(Status::Added)),
+                (Some(Status::Added), Some(Status::Pending)),
+                (Some(Status::Pending), Some(Status::Resolved)),
+            ]
+        );
+        assert_eq!(lifecycle.status(BOB, HASH), Some(Status::Resolved));
+        assert_eq!(lifecycle.get(BOB, HASH).unwrap().source_id, Some(B256::repeat_byte(7)));
+        assert_eq!(lifecycle.with_status(Status::Resolved).count(), 2);
+
+        // Another blob of the same subscribers is unaffected.
+        let other = B256::repeat_byte(0xbb);
+        lifecycle.apply(&blob_added(ALICE, other)).unwrap();
+        assert_eq!(lifecycle.status(ALICE, other), Some(Status::Added));
+        assert_eq!(lifecycle.status(ALICE, HASH), Some(Status::Resolved));
+    }
+
+    #[test]
+    fn failure_and_retry_apply_to_all_subscribers() {
+        let mut lifecycle = Lifecycle::default();
+        let events = [added(ALICE), added(BOB), pending(BOB), finalized(BOB, false)];
+        steps(&mut lifecycle, &events);
+        assert_eq!(lifecycle.status(ALICE, HASH), Some(Status::Failed));
+
+        // Renewing either subscription retries the blob for both.
+        assert_eq!(
+            steps(&mut lifecycle, &[added(ALICE)]),
+            [(Some(Status::Failed), Some(Status::Added))]
+        );
+        assert_eq!(lifecycle.status(BOB, HASH), Some(Status::Added));
+        assert_eq!(lifecycle.get(BOB, HASH).unwrap().source_id, None);
+        steps(&mut lifecycle, &[pending(ALICE)]);
+        assert_eq!(lifecycle.with_status(Status::Pending).count(), 2);
+    }
+
+    #[test]
+    fn illegal_transitions() {
+        let mut lifecycle = Lifecycle::default();
+        for event in [pending(ALICE), finalized(ALICE, true), deleted(ALICE)] {
+            let err = lifecycle.apply(&event).err().unwrap();
+            assert!(err.to_string().contains("blob is not tracked"), "{}", err);
+        }
+
+        steps(&mut lifecycle, &[added(ALICE)]);
+        let err = lifecycle.apply(&finalized(ALICE, true)).err().unwrap();
+        assert!(err.to_string().contains("blob is added, but only pending"), "{}", err);
+
+        steps(&mut lifecycle, &[pending(ALICE)]);
+        assert!(lifecycle.apply(&pending(ALICE)).is_err());
+        steps(&mut lifecycle, &[finalized(ALICE, true)]);
+        assert!(lifecycle.apply(&pending(ALICE)).is_err());
+        assert!(lifecycle.apply(&finalized(ALICE, false)).is_err());
+        // Rejected events leave the state unchanged.
+        assert_eq!(lifecycle.status(ALICE, HASH), Some(Status::Resolved));
+    }
+
+    #[test]
+    fn status_codes() {
+        for status in [Status::Added, Status::Pending, Status::Resolved, Status::Failed] {
+            assert_eq!(Status::try_from(u8::from(status)).unwrap(), status);
+        }
+        assert!(Status::try_from(4).is_err());
+        assert!(Status::Failed.is_finalized() && !Status::Pending.is_finalized());
+    }
+}
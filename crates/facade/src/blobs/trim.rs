@@ -0,0 +1,310 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::path::Path;
+
+use alloy_primitives::{Address, B256};
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+
+use super::trimBlobExpiriesCall;
+use crate::executor::{CallExecutor, ExecutionError};
+use crate::persist;
+
+/// Where a `trimBlobExpiries` run stands. Persist it to resume an interrupted run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrimCursor {
+    pub subscriber: Address,
+    /// The `startingHash` of the next call.
+    pub next_key: B256,
+    /// The `limit` of the next call.
+    pub limit: u32,
+    /// Blobs processed so far, across all calls.
+    pub processed: u64,
+    /// Whether the facade returned a zero `nextKey`, i.e. there is nothing left to trim.
+    pub done: bool,
+}
+
+impl TrimCursor {
+    /// Returns a cursor that starts trimming `subscriber`'s blobs from the beginning.
+    pub fn new(subscriber: Address, limit: u32) -> Self {
+        Self {
+            subscriber,
+            next_key: B256::ZERO,
+            limit,
+            processed: 0,
+            done: false,
+        }
+    }
+
+    /// Loads a cursor from a JSON file, or returns `None` if the file doesn't exist.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Option<Self>> {
+        let path = path.as_ref();
+        let Some(bytes) = persist::read_if_exists(path)? else {
+            return Ok(None);
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .with_context(|| format!("invalid trim cursor in {}", path.display()))
+    }
+
+    /// Stores the cursor as JSON, replacing the file atomically.
+    pub fn store(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        persist::write_atomic(path.as_ref(), &serde_json::to_vec_pretty(self)?)
+    }
+}
+
+/// Progress reported after each successful `trimBlobExpiries` call.
+#[derive(Clone, Copy, Debug)]
+pub struct TrimProgress {
+    /// The cursor after the call. Persisting it makes the run resumable.
+    pub cursor: TrimCursor,
+    /// The `limit` used by the call.
+    pub limit: u32,
+    /// Blobs processed by the call.
+    pub processed: u32,
+    pub gas_used: u64,
+}
+
+/// Calls `trimBlobExpiries` until the facade returns a zero `nextKey`.
+///
+/// After each call, the next `limit` is scaled so that a call uses about
+/// [`target_gas`](Self::target_gas), bounded by [`limits`](Self::limits). If a call runs out of
+/// gas or reverts, the limit is halved and the call retried, until the minimum limit fails too.
+/// Other errors, e.g. from the transport, are returned as is.
+#[derive(Clone, Debug)]
+pub struct TrimDriver {
+    facade: Address,
+    cursor: TrimCursor,
+    target_gas: u64,
+    min_limit: u32,
+    max_limit: u32,
+}
+
+impl TrimDriver {
+    pub const DEFAULT_LIMIT: u32 = 100;
+    pub const DEFAULT_TARGET_GAS: u64 = 5_000_000;
+
+    /// Returns a driver that trims `subscriber`'s blobs from the beginning, calling the blobs
+    /// facade at `facade`.
+    pub fn new(facade: Address, subscriber: Address) -> Self {
+        Self::resume(facade, TrimCursor::new(subscriber, Self::DEFAULT_LIMIT))
+    }
+
+    /// Returns a driver that continues from a persisted cursor.
+    pub fn resume(facade: Address, cursor: TrimCursor) -> Self {
+        Self {
+            facade,
+            cursor,
+            target_gas: Self::DEFAULT_TARGET_GAS,
+            min_limit: 1,
+            max_limit: u32::MAX,
+        }
+    }
+
+    /// Sets the gas each call should use.
+    pub fn target_gas(mut self, gas: u64) -> Self {
+        self.target_gas = gas;
+        self
+    }
+
+    /// Bounds the `limit` passed to the facade. The current limit is clamped into the range.
+    pub fn limits(mut self, min: u32, max: u32) -> Self {
+        self.min_limit = min.max(1);
+        self.max_limit = max.max(self.min_limit);
+        self.cursor.limit = self.cursor.limit.clamp(self.min_limit, self.max_limit);
+        self
+    }
+
+    pub fn cursor(&self) -> &TrimCursor {
+        &self.cursor
+    }
+
+    /// Runs until done, calling `on_progress` after each call. An error returned by
+    /// `on_progress`, e.g. from [`TrimCursor::store`], stops the run.
+    pub fn run<E, F>(
+        &mut self,
+        executor: &mut E,
+        mut on_progress: F,
+    ) -> anyhow::Result<TrimCursor>
+    where
+        E: CallExecutor,
+        F: FnMut(&TrimProgress) -> anyhow::Result<()>,
+    {
+        while !self.cursor.done {
+            let progress = self.step(executor)?;
+            on_progress(&progress)?;
+        }
+        Ok(self.cursor)
+    }
+
+    /// Makes a single successful call, retrying with smaller limits if a call runs out of gas or
+    /// reverts.
+    pub fn step<E: CallExecutor>(&mut self, executor: &mut E) -> anyhow::Result<TrimProgress> {
+        if self.cursor.done {
+            bail!("trim of {} is already done", self.cursor.subscriber);
+        }
+        loop {
+            let limit = self.cursor.limit;
+            let call = trimBlobExpiriesCall {
+                subscriber: self.cursor.subscriber,
+                startingHash: self.cursor.next_key,
+                limit,
+            };
+            let executed = match executor.call(self.facade, &call) {
+                Ok(executed) => executed,
+                Err(e) if ExecutionError::find(&e).is_none() => return Err(e),
+                Err(_) if limit > self.min_limit => {
+                    self.cursor.limit = (limit / 2).max(self.min_limit);
+                    continue;
+                }
+                Err(e) => {
+                    return Err(e.context(format!(
+                        "trimBlobExpiries failed at {} with limit {}",
+                        self.cursor.next_key, limit
+                    )))
+                }
+            };
+            let result = executed.ret._0;
+            if result.nextKey != B256::ZERO
+                && result.nextKey == self.cursor.next_key
+                && result.processed == 0
+            {
+                bail!("trimBlobExpiries made no progress at {}", self.cursor.next_key);
+            }
+            self.cursor.next_key = result.nextKey;
+            self.cursor.done = result.nextKey == B256::ZERO;
+            self.cursor.processed += u64::from(result.processed);
+            self.cursor.limit = self.next_limit(limit, result.processed, executed.gas_used);
+            return Ok(TrimProgress {
+                cursor: self.cursor,
+                limit,
+                processed: result.processed,
+                gas_used: executed.gas_used,
+            });
+        }
+    }
+
+    fn next_limit(&self, limit: u32, processed: u32, gas_used: u64) -> u32 {
+        if processed == 0 || gas_used == 0 {
+            return limit;
+        }
+        let per_blob = (gas_used / u64::from(processed)).max(1);
+        let next = (self.target_gas / per_blob).min(u64::from(u32::MAX)) as u32;
+        next.clamp(self.min_limit, self.max_limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::Bytes;
+    use alloy_sol_types::SolCall;
+
+    use super::*;
+    use crate::blobs::TrimBlobExpiries;
+    use crate::executor::Execution;
+
+    /// Trims `total` blobs, using `gas_per_blob` per blob and running out of gas above
+    /// `max_limit`.
+    struct FakeFacade {
+        total: u32,
+        trimmed: u32,
+        gas_per_blob: u64,
+        max_limit: u32,
+        /// Fails the next call with a transport error.
+        disconnect: bool,
+        limits: Vec<u32>,
+    }
+
+    impl FakeFacade {
+        fn new(total: u32, max_limit: u32) -> Self {
+            Self {
+                total,
+                trimmed: 0,
+                gas_per_blob: 1000,
+                max_limit,
+                disconnect: false,
+                limits: Vec::new(),
+            }
+        }
+    }
+
+    impl CallExecutor for FakeFacade {
+        fn execute(&mut self, _to: Address, calldata: Bytes) -> anyhow::Result<Execution> {
+            let call = trimBlobExpiriesCall::abi_decode(&calldata, true)?;
+            self.limits.push(call.limit);
+            if std::mem::take(&mut self.disconnect) {
+                bail!("connection refused");
+            }
+            if call.limit > self.max_limit {
+                return Err(ExecutionError::OutOfGas.into());
+            }
+            let processed = call.limit.min(self.total - self.trimmed);
+            self.trimmed += processed;
+            let next_key = if self.trimmed == self.total {
+                B256::ZERO
+            } else {
+                B256::with_last_byte(self.trimmed as u8)
+            };
+            let ret = TrimBlobExpiries {
+                processed,
+                nextKey: next_key,
+            };
+            Ok(Execution {
+                output: trimBlobExpiriesCall::abi_encode_returns(&(ret,)).into(),
+                gas_used: u64::from(processed) * self.gas_per_blob,
+            })
+        }
+    }
+
+    #[test]
+    fn halves_limit_when_out_of_gas() {
+        let mut facade = FakeFacade::new(10, 3);
+        let mut driver = TrimDriver::new(Address::ZERO, Address::ZERO)
+            .target_gas(3000)
+            .limits(1, 16);
+        let cursor = driver.run(&mut facade, |_| Ok(())).unwrap();
+        assert!(cursor.done);
+        assert_eq!(cursor.processed, 10);
+        // 16, 8 and 4 run out of gas, then the limit follows the target gas.
+        assert_eq!(facade.limits, [16, 8, 4, 2, 3, 3, 3]);
+    }
+
+    #[test]
+    fn fails_when_min_limit_runs_out_of_gas() {
+        let mut facade = FakeFacade::new(10, 0);
+        let mut driver = TrimDriver::new(Address::ZERO, Address::ZERO).limits(1, 4);
+        let err = driver.step(&mut facade).err().unwrap();
+        assert_eq!(ExecutionError::find(&err), Some(&ExecutionError::OutOfGas));
+        assert_eq!(facade.limits, [4, 2, 1]);
+        assert_eq!(driver.cursor().limit, 1);
+    }
+
+    #[test]
+    fn returns_other_errors_as_is() {
+        let mut facade = FakeFacade::new(10, 10);
+        facade.disconnect = true;
+        let mut driver = TrimDriver::new(Address::ZERO, Address::ZERO).limits(1, 8);
+        let err = driver.step(&mut facade).err().unwrap();
+        assert_eq!(err.to_string(), "connection refused");
+        assert_eq!(facade.limits, [8]);
+        assert_eq!(driver.cursor().limit, 8);
+        // The cursor is unchanged, so the step can be retried.
+        let progress = driver.step(&mut facade).unwrap();
+        assert_eq!(progress.limit, 8);
+        assert_eq!(progress.processed, 8);
+    }
+
+    #[test]
+    fn cursor_round_trip() {
+        let dir = std::env::temp_dir().join(format!("recall-trim-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cursor.json");
+        assert_eq!(TrimCursor::load(&path).unwrap(), None);
+        let cursor = TrimCursor::new(Address::repeat_byte(1), 50);
+        cursor.store(&path).unwrap();
+        assert_eq!(TrimCursor::load(&path).unwrap(), Some(cursor));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
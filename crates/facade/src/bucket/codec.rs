@@ -0,0 +1,148 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::{BTreeMap, HashMap};
+
+use alloy_primitives::{Bytes, B256};
+
+use super::{KeyValue, ObjectAdded, ObjectDeleted, ObjectMetadataUpdated};
+use crate::ipld;
+
+/// Decodes IPLD-encoded event metadata into key-value pairs, sorted by key.
+pub fn decode_metadata(bytes: &[u8]) -> anyhow::Result<Vec<KeyValue>> {
+    ipld::decode_key_values(bytes)
+}
+
+/// Encodes key-value pairs as event metadata. If a key occurs more than once, the last value wins.
+pub fn encode_metadata(metadata: &[KeyValue]) -> anyhow::Result<Bytes> {
+    Ok(ipld::encode_key_values(metadata)?.into())
+}
+
+/// Converts metadata entries to key-value pairs in key order.
+pub(crate) fn key_values(metadata: &BTreeMap<String, String>) -> Vec<KeyValue> {
+    metadata
+        .iter()
+        .map(|(key, value)| KeyValue {
+            key: key.clone(),
+            value: value.clone(),
+        })
+        .collect()
+}
+
+/// Collects key-value pairs into metadata entries. If a key occurs more than once, the last value
+/// wins.
+pub(crate) fn metadata_entries(metadata: &[KeyValue]) -> BTreeMap<String, String> {
+    metadata
+        .iter()
+        .map(|kv| (kv.key.clone(), kv.value.clone()))
+        .collect()
+}
+
+impl ObjectAdded {
+    pub fn from_parts(key: &str, blob_hash: B256, metadata: &[KeyValue]) -> anyhow::Result<Self> {
+        Ok(Self {
+            key: ipld::encode_key(key).into(),
+            blobHash: blob_hash,
+            metadata: encode_metadata(metadata)?,
+        })
+    }
+
+    /// Returns the object key, failing if it isn't valid UTF-8.
+    pub fn object_key(&self) -> anyhow::Result<String> {
+        ipld::decode_key(&self.key)
+    }
+
+    pub fn metadata_map(&self) -> anyhow::Result<HashMap<String, String>> {
+        ipld::decode_metadata(&self.metadata)
+    }
+
+    pub fn metadata_key_values(&self) -> anyhow::Result<Vec<KeyValue>> {
+        decode_metadata(&self.metadata)
+    }
+}
+
+impl ObjectMetadataUpdated {
+    pub fn from_parts(key: &str, metadata: &[KeyValue]) -> anyhow::Result<Self> {
+        Ok(Self {
+            key: ipld::encode_key(key).into(),
+            metadata: encode_metadata(metadata)?,
+        })
+    }
+
+    /// Returns the object key, failing if it isn't valid UTF-8.
+    pub fn object_key(&self) -> anyhow::Result<String> {
+        ipld::decode_key(&self.key)
+    }
+
+    pub fn metadata_map(&self) -> anyhow::Result<HashMap<String, String>> {
+        ipld::decode_metadata(&self.metadata)
+    }
+
+    pub fn metadata_key_values(&self) -> anyhow::Result<Vec<KeyValue>> {
+        decode_metadata(&self.metadata)
+    }
+}
+
+impl ObjectDeleted {
+    pub fn from_parts(key: &str, blob_hash: B256) -> Self {
+        Self {
+            key: ipld::encode_key(key).into(),
+            blobHash: blob_hash,
+        }
+    }
+
+    /// Returns the object key, failing if it isn't valid UTF-8.
+    pub fn object_key(&self) -> anyhow::Result<String> {
+        ipld::decode_key(&self.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_sol_types::SolEvent;
+
+    use super::*;
+
+    fn kv(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn pairs(metadata: &[KeyValue]) -> Vec<(&str, &str)> {
+        metadata.iter().map(|kv| (kv.key.as_str(), kv.value.as_str())).collect()
+    }
+
+    #[test]
+    fn object_added_round_trip() {
+        let metadata = [kv("type", "image/png"), kv("owner", "alice")];
+        let event = ObjectAdded::from_parts("a.png", B256::repeat_byte(1), &metadata).unwrap();
+        let decoded = ObjectAdded::decode_log_data(&event.encode_log_data(), true).unwrap();
+        assert_eq!(decoded.object_key().unwrap(), "a.png");
+        assert_eq!(decoded.blobHash, B256::repeat_byte(1));
+        assert_eq!(
+            pairs(&decoded.metadata_key_values().unwrap()),
+            [("owner", "alice"), ("type", "image/png")]
+        );
+        assert_eq!(decoded.metadata_map().unwrap()["owner"], "alice");
+    }
+
+    #[test]
+    fn metadata_updated_round_trip() {
+        let event = ObjectMetadataUpdated::from_parts("a", &[kv("k", "")]).unwrap();
+        assert_eq!(event.object_key().unwrap(), "a");
+        assert_eq!(pairs(&event.metadata_key_values().unwrap()), [("k", "")]);
+    }
+
+    #[test]
+    fn non_utf8_object_key() {
+        let event = ObjectDeleted {
+            key: Bytes::from_static(&[0xc3, 0x28]),
+            blobHash: B256::ZERO,
+        };
+        let err = event.object_key().err().unwrap();
+        assert!(err.to_string().starts_with("object key 0xc328 is not valid UTF-8"), "{}", err);
+        assert_eq!(ObjectDeleted::from_parts("b", B256::ZERO).object_key().unwrap(), "b");
+    }
+}
@@ -0,0 +1,374 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use alloy_primitives::B256;
+use anyhow::{anyhow, bail, Context};
+use serde::{Deserialize, Serialize};
+
+use super::codec::key_values;
+use super::model::query;
+use super::{
+    Events, KeyValue, ObjectAdded, ObjectDeleted, ObjectMetadataUpdated, ObjectState, Query,
+};
+use crate::persist;
+
+/// An object as known from bucket events.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedObject {
+    pub blob_hash: B256,
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl IndexedObject {
+    pub fn metadata_key_values(&self) -> Vec<KeyValue> {
+        key_values(&self.metadata)
+    }
+}
+
+/// The position of a log in the chain, used to skip logs already applied after a restart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogPosition {
+    pub block: u64,
+    pub log_index: u64,
+}
+
+/// A local mirror of a bucket, built by folding its `ObjectAdded`, `ObjectMetadataUpdated` and
+/// `ObjectDeleted` events.
+///
+/// Events must be applied in chain order, starting from the bucket's creation: updating or
+/// deleting a key the index doesn't know is an error. The events don't carry object sizes or
+/// expiries, so [`query_objects`](Self::query_objects) returns them as zero.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjectIndex {
+    objects: BTreeMap<String, IndexedObject>,
+    position: Option<LogPosition>,
+}
+
+impl ObjectIndex {
+    pub const CHECKPOINT_VERSION: u32 = 1;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&IndexedObject> {
+        self.objects.get(key)
+    }
+
+    /// Iterates over all objects in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &IndexedObject)> {
+        self.objects.iter().map(|(k, o)| (k.as_str(), o))
+    }
+
+    /// Returns the position of the last log applied with [`apply_at`](Self::apply_at).
+    pub fn position(&self) -> Option<LogPosition> {
+        self.position
+    }
+
+    /// Applies a bucket event. If it fails, the index is unchanged.
+    pub fn apply(&mut self, event: &Events) -> anyhow::Result<()> {
+        match event {
+            Events::ObjectAdded(e) => self.apply_added(e),
+            Events::ObjectMetadataUpdated(e) => self.apply_metadata_updated(e),
+            Events::ObjectDeleted(e) => self.apply_deleted(e),
+        }
+    }
+
+    /// Applies the event of the log at `position`, unless the index is already at or past it.
+    /// Returns whether the event was applied.
+    pub fn apply_at(&mut self, position: LogPosition, event: &Events) -> anyhow::Result<bool> {
+        if self.position.is_some_and(|p| position <= p) {
+            return Ok(false);
+        }
+        self.apply(event)?;
+        self.position = Some(position);
+        Ok(true)
+    }
+
+    /// Adds an object, replacing any object with the same key.
+    pub fn apply_added(&mut self, event: &ObjectAdded) -> anyhow::Result<()> {
+        let key = event.object_key()?;
+        let metadata = event.metadata_map()?.into_iter().collect();
+        self.objects.insert(
+            key,
+            IndexedObject {
+                blob_hash: event.blobHash,
+                metadata,
+            },
+        );
+        Ok(())
+    }
+
+    /// Replaces an object's metadata.
+    ///
+    /// `updateObjectMetadata` merges its entries into the object's metadata, removing keys whose
+    /// value is empty, and the event carries the object's metadata after the merge. Replacing
+    /// therefore gives the same metadata as [`BucketModel`](super::BucketModel).
+    pub fn apply_metadata_updated(&mut self, event: &ObjectMetadataUpdated) -> anyhow::Result<()> {
+        let key = event.object_key()?;
+        let metadata = event.metadata_map()?.into_iter().collect();
+        let object = self
+            .objects
+            .get_mut(&key)
+            .ok_or_else(|| anyhow!("metadata updated for unknown key {:?}", key))?;
+        object.metadata = metadata;
+        Ok(())
+    }
+
+    pub fn apply_deleted(&mut self, event: &ObjectDeleted) -> anyhow::Result<()> {
+        let key = event.object_key()?;
+        match self.objects.get(&key) {
+            None => bail!("unknown key {:?} deleted", key),
+            Some(object) if object.blob_hash != event.blobHash => bail!(
+                "key {:?} deleted with blob hash {}, but the index has {}",
+                key,
+                event.blobHash,
+                object.blob_hash
+            ),
+            Some(_) => {
+                self.objects.remove(&key);
+                Ok(())
+            }
+        }
+    }
+
+    /// Lists objects like `queryObjects(prefix, delimiter, startKey, limit)`, with the semantics
+    /// of [`BucketModel`](super::BucketModel).
+    pub fn query_objects(
+        &self,
+        prefix: &str,
+        delimiter: &str,
+        start_key: &str,
+        limit: u64,
+    ) -> Query {
+        query(&self.objects, prefix, delimiter, start_key, limit, |o| ObjectState {
+            blobHash: o.blob_hash,
+            size: 0,
+            expiry: 0,
+            metadata: o.metadata_key_values(),
+        })
+    }
+
+    pub fn checkpoint(&self) -> IndexCheckpoint {
+        IndexCheckpoint {
+            version: Self::CHECKPOINT_VERSION,
+            position: self.position,
+            objects: self.objects.clone(),
+        }
+    }
+
+    pub fn restore(checkpoint: IndexCheckpoint) -> anyhow::Result<Self> {
+        if checkpoint.version != Self::CHECKPOINT_VERSION {
+            bail!(
+                "unsupported index checkpoint version {}, expected {}",
+                checkpoint.version,
+                Self::CHECKPOINT_VERSION
+            );
+        }
+        Ok(Self {
+            objects: checkpoint.objects,
+            position: checkpoint.position,
+        })
+    }
+
+    /// Loads an index from a JSON checkpoint file, or returns `None` if the file doesn't exist.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Option<Self>> {
+        let path = path.as_ref();
+        let Some(bytes) = persist::read_if_exists(path)? else {
+            return Ok(None);
+        };
+        let checkpoint: IndexCheckpoint = serde_json::from_slice(&bytes)
+            .with_context(|| format!("invalid index checkpoint in {}", path.display()))?;
+        Self::restore(checkpoint).map(Some)
+    }
+
+    /// Stores a JSON checkpoint, replacing the file atomically.
+    pub fn store(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        persist::write_atomic(path.as_ref(), &serde_json::to_vec(&self.checkpoint())?)
+    }
+}
+
+/// The persisted form of an [`ObjectIndex`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexCheckpoint {
+    pub version: u32,
+    /// The position of the last applied log. Resume reading logs after it.
+    pub position: Option<LogPosition>,
+    pub objects: BTreeMap<String, IndexedObject>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bucket::codec::metadata_entries;
+    use crate::bucket::{addObject_1Call, BucketModel};
+
+    fn kv(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn added(key: &str, hash: u8) -> Events {
+        let event = ObjectAdded::from_parts(key, B256::repeat_byte(hash), &[kv("k", "v")]);
+        Events::ObjectAdded(event.unwrap())
+    }
+
+    fn index(keys: &[&str]) -> ObjectIndex {
+        let mut index = ObjectIndex::new();
+        for key in keys {
+            index.apply(&added(key, 1)).unwrap();
+        }
+        index
+    }
+
+    fn keys(query: &Query) -> Vec<&str> {
+        query.objects.iter().map(|o| o.key.as_str()).collect()
+    }
+
+    #[test]
+    fn query_matches_model_semantics() {
+        let index = index(&["a/1", "a/2", "b", "c/1", "d", "e"]);
+        let query = index.query_objects("", "/", "", 2);
+        assert_eq!(keys(&query), ["b", "d"]);
+        assert_eq!(query.commonPrefixes, ["a/", "c/"]);
+        assert_eq!(query.nextKey, "e");
+        assert_eq!(query.objects[0].state.size, 0);
+
+        let query = index.query_objects("a/", "/", "", 0);
+        assert_eq!(keys(&query), ["a/1", "a/2"]);
+        assert!(query.commonPrefixes.is_empty());
+        assert_eq!(query.nextKey, "");
+    }
+
+    #[test]
+    fn pages_across_common_prefixes() {
+        let index = index(&["a/1", "a/2", "b", "c/1", "d"]);
+        let (mut objects, mut prefixes) = (Vec::new(), Vec::new());
+        let mut start_key = String::new();
+        loop {
+            let query = index.query_objects("", "/", &start_key, 1);
+            objects.extend(keys(&query).into_iter().map(str::to_string));
+            prefixes.extend(query.commonPrefixes);
+            if query.nextKey.is_empty() {
+                break;
+            }
+            start_key = query.nextKey;
+        }
+        prefixes.dedup();
+        assert_eq!(objects, ["b", "d"]);
+        assert_eq!(prefixes, ["a/", "c/"]);
+    }
+
+    #[test]
+    fn overwrite_and_delete() {
+        let mut index = index(&["a"]);
+        index.apply(&added("a", 2)).unwrap();
+        assert_eq!(index.get("a").unwrap().blob_hash, B256::repeat_byte(2));
+
+        let stale = Events::ObjectDeleted(ObjectDeleted::from_parts("a", B256::repeat_byte(1)));
+        assert!(index.apply(&stale).is_err());
+        assert_eq!(index.len(), 1);
+        let deleted = Events::ObjectDeleted(ObjectDeleted::from_parts("a", B256::repeat_byte(2)));
+        index.apply(&deleted).unwrap();
+        assert!(index.is_empty());
+        assert!(index.apply(&deleted).is_err());
+    }
+
+    #[test]
+    fn metadata_updates_match_model() {
+        let mut model = BucketModel::new(10);
+        model
+            .add_object(&addObject_1Call {
+                source: B256::ZERO,
+                key: "a".to_string(),
+                hash: B256::repeat_byte(1),
+                recoveryHash: B256::ZERO,
+                size: 1,
+                ttl: 0,
+                metadata: vec![kv("keep", "1"), kv("drop", "2")],
+                overwrite: false,
+            })
+            .unwrap();
+        let mut index = ObjectIndex::new();
+        let value = model.get_object("a").unwrap();
+        let event = ObjectAdded::from_parts("a", value.blobHash, &value.metadata).unwrap();
+        index.apply_added(&event).unwrap();
+
+        let update = [kv("drop", ""), kv("new", "3")];
+        model.update_object_metadata("a", &update).unwrap();
+        // The event carries the metadata after the update.
+        let metadata = model.get_object("a").unwrap().metadata;
+        let event = ObjectMetadataUpdated::from_parts("a", &metadata).unwrap();
+        index.apply_metadata_updated(&event).unwrap();
+
+        let expected = BTreeMap::from([
+            ("keep".to_string(), "1".to_string()),
+            ("new".to_string(), "3".to_string()),
+        ]);
+        assert_eq!(index.get("a").unwrap().metadata, expected);
+        let from_model = model.get_object("a").unwrap().metadata;
+        assert_eq!(metadata_entries(&from_model), expected);
+
+        let unknown = ObjectMetadataUpdated::from_parts("b", &[]).unwrap();
+        assert!(index.apply_metadata_updated(&unknown).is_err());
+    }
+
+    #[test]
+    fn apply_at_skips_applied_logs() {
+        let mut index = ObjectIndex::new();
+        let position = LogPosition {
+            block: 5,
+            log_index: 1,
+        };
+        assert!(index.apply_at(position, &added("a", 1)).unwrap());
+        assert!(!index.apply_at(position, &added("b", 1)).unwrap());
+        let next = LogPosition {
+            block: 6,
+            log_index: 0,
+        };
+        assert!(index.apply_at(next, &added("b", 1)).unwrap());
+        assert_eq!(index.position(), Some(next));
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn checkpoint_round_trip() {
+        let mut index = index(&["a/1", "b"]);
+        index
+            .apply_at(
+                LogPosition {
+                    block: 1,
+                    log_index: 0,
+                },
+                &added("c", 3),
+            )
+            .unwrap();
+        let dir = std::env::temp_dir().join(format!("recall-index-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.json");
+        assert!(ObjectIndex::load(&path).unwrap().is_none());
+        index.store(&path).unwrap();
+        assert_eq!(ObjectIndex::load(&path).unwrap().unwrap(), index);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let mut checkpoint = index.checkpoint();
+        checkpoint.version += 1;
+        assert!(ObjectIndex::restore(checkpoint).is_err());
+    }
+}
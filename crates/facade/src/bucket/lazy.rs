@@ -0,0 +1,261 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Zero-copy views over raw bucket facade logs.
+//!
+//! Unlike [`Events`](super::Events), a view doesn't decode the log up front. Construction only
+//! checks the event signature and the length of the static data section. The dynamic `key` and
+//! `metadata` fields are located on demand and returned as slices of the log data.
+//!
+//! Views don't validate word padding. Use [`LazyEvent::decode`] (or the `Events` decoder with
+//! `validate = true`) if that matters.
+
+use alloy_primitives::{LogData, B256};
+use alloy_sol_types::{Error, Result, SolEvent};
+
+use super::{Events, ObjectAdded, ObjectDeleted, ObjectMetadataUpdated};
+use crate::abi;
+
+macro_rules! bucket_event_view {
+    ($(#[$doc:meta])* $view:ident, $event:ident, $words:expr) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Debug)]
+        pub struct $view<'a> {
+            data: &'a [u8],
+        }
+
+        impl<'a> $view<'a> {
+            /// Number of static data words in the event.
+            const WORDS: usize = $words;
+
+            /// Wraps a raw log if it carries the event's signature hash and enough data.
+            pub fn new(topics: &'a [B256], data: &'a [u8]) -> Option<Self> {
+                if topics.len() != 1
+                    || topics[0] != <$event as SolEvent>::SIGNATURE_HASH
+                    || data.len() < Self::WORDS * abi::WORD
+                {
+                    return None;
+                }
+                Some(Self { data })
+            }
+
+            /// Wraps a [`LogData`] if it carries the event's signature hash and enough data.
+            pub fn from_log(log: &'a LogData) -> Option<Self> {
+                Self::new(log.topics(), &log.data)
+            }
+
+            /// The raw object key bytes.
+            pub fn key(&self) -> Result<&'a [u8]> {
+                abi::dyn_bytes(self.data, 0)
+            }
+
+            /// The object key as UTF-8.
+            pub fn key_str(&self) -> Result<&'a str> {
+                std::str::from_utf8(self.key()?).map_err(|e| Error::custom(e.to_string()))
+            }
+        }
+    };
+}
+
+bucket_event_view!(
+    /// A lazily decoded [`ObjectAdded`] log.
+    ObjectAddedView,
+    ObjectAdded,
+    3
+);
+
+impl<'a> ObjectAddedView<'a> {
+    /// The object blob blake3 hash.
+    pub fn blob_hash(&self) -> &'a B256 {
+        // Length was checked in `new`.
+        abi::word_b256(self.data, 1).unwrap()
+    }
+
+    /// The raw IPLD-encoded object metadata.
+    pub fn metadata(&self) -> Result<&'a [u8]> {
+        abi::dyn_bytes(self.data, 2)
+    }
+
+    /// Decodes all fields into an owned [`ObjectAdded`].
+    pub fn decode(&self) -> Result<ObjectAdded> {
+        Ok(ObjectAdded {
+            key: self.key()?.to_vec().into(),
+            blobHash: *self.blob_hash(),
+            metadata: self.metadata()?.to_vec().into(),
+        })
+    }
+}
+
+bucket_event_view!(
+    /// A lazily decoded [`ObjectMetadataUpdated`] log.
+    ObjectMetadataUpdatedView,
+    ObjectMetadataUpdated,
+    2
+);
+
+impl<'a> ObjectMetadataUpdatedView<'a> {
+    /// The raw IPLD-encoded object metadata.
+    pub fn metadata(&self) -> Result<&'a [u8]> {
+        abi::dyn_bytes(self.data, 1)
+    }
+
+    /// Decodes all fields into an owned [`ObjectMetadataUpdated`].
+    pub fn decode(&self) -> Result<ObjectMetadataUpdated> {
+        Ok(ObjectMetadataUpdated {
+            key: self.key()?.to_vec().into(),
+            metadata: self.metadata()?.to_vec().into(),
+        })
+    }
+}
+
+bucket_event_view!(
+    /// A lazily decoded [`ObjectDeleted`] log.
+    ObjectDeletedView,
+    ObjectDeleted,
+    2
+);
+
+impl<'a> ObjectDeletedView<'a> {
+    /// The object blob blake3 hash.
+    pub fn blob_hash(&self) -> &'a B256 {
+        // Length was checked in `new`.
+        abi::word_b256(self.data, 1).unwrap()
+    }
+
+    /// Decodes all fields into an owned [`ObjectDeleted`].
+    pub fn decode(&self) -> Result<ObjectDeleted> {
+        Ok(ObjectDeleted {
+            key: self.key()?.to_vec().into(),
+            blobHash: *self.blob_hash(),
+        })
+    }
+}
+
+/// A lazily decoded bucket facade log.
+#[derive(Clone, Copy, Debug)]
+pub enum LazyEvent<'a> {
+    ObjectAdded(ObjectAddedView<'a>),
+    ObjectMetadataUpdated(ObjectMetadataUpdatedView<'a>),
+    ObjectDeleted(ObjectDeletedView<'a>),
+}
+
+impl<'a> LazyEvent<'a> {
+    /// Wraps a raw log if it is one of the bucket facade events.
+    pub fn new(topics: &'a [B256], data: &'a [u8]) -> Option<Self> {
+        match *topics.first()? {
+            <ObjectAdded as SolEvent>::SIGNATURE_HASH => {
+                ObjectAddedView::new(topics, data).map(Self::ObjectAdded)
+            }
+            <ObjectMetadataUpdated as SolEvent>::SIGNATURE_HASH => {
+                ObjectMetadataUpdatedView::new(topics, data).map(Self::ObjectMetadataUpdated)
+            }
+            <ObjectDeleted as SolEvent>::SIGNATURE_HASH => {
+                ObjectDeletedView::new(topics, data).map(Self::ObjectDeleted)
+            }
+            _ => None,
+        }
+    }
+
+    /// Wraps a [`LogData`] if it is one of the bucket facade events.
+    pub fn from_log(log: &'a LogData) -> Option<Self> {
+        Self::new(log.topics(), &log.data)
+    }
+
+    /// The raw object key bytes.
+    pub fn key(&self) -> Result<&'a [u8]> {
+        match self {
+            Self::ObjectAdded(v) => v.key(),
+            Self::ObjectMetadataUpdated(v) => v.key(),
+            Self::ObjectDeleted(v) => v.key(),
+        }
+    }
+
+    /// Decodes all fields into an owned [`Events`] value.
+    pub fn decode(&self) -> Result<Events> {
+        Ok(match self {
+            Self::ObjectAdded(v) => Events::ObjectAdded(v.decode()?),
+            Self::ObjectMetadataUpdated(v) => Events::ObjectMetadataUpdated(v.decode()?),
+            Self::ObjectDeleted(v) => Events::ObjectDeleted(v.decode()?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, Bytes, IntoLogData, Log};
+    use alloy_sol_types::SolEventInterface;
+
+    use super::*;
+    use crate::bucket::KeyValue;
+
+    fn logs() -> Vec<LogData> {
+        let metadata = [KeyValue {
+            key: "type".to_string(),
+            value: "text/plain".to_string(),
+        }];
+        vec![
+            ObjectAdded::from_parts("a/b.txt", B256::repeat_byte(1), &metadata)
+                .unwrap()
+                .encode_log_data(),
+            ObjectMetadataUpdated::from_parts("a/b.txt", &metadata)
+                .unwrap()
+                .encode_log_data(),
+            ObjectDeleted::from_parts("a/b.txt", B256::repeat_byte(2)).encode_log_data(),
+        ]
+    }
+
+    /// Decodes with the eager decoder and re-encodes, to compare all fields.
+    fn eager(log: &LogData) -> LogData {
+        let log = Log {
+            address: Address::ZERO,
+            data: log.clone(),
+        };
+        Events::decode_log(&log, true).unwrap().data.into_log_data()
+    }
+
+    #[test]
+    fn decodes_like_events() {
+        for log in logs() {
+            let lazy = LazyEvent::from_log(&log).unwrap();
+            assert_eq!(lazy.decode().unwrap().into_log_data(), eager(&log));
+            assert_eq!(lazy.key().unwrap(), b"a/b.txt");
+        }
+    }
+
+    #[test]
+    fn accessors_match_fields() {
+        let logs = logs();
+        let view = ObjectAddedView::from_log(&logs[0]).unwrap();
+        let added = view.decode().unwrap();
+        assert_eq!(view.key_str().unwrap(), "a/b.txt");
+        assert_eq!(view.blob_hash(), &B256::repeat_byte(1));
+        assert_eq!(view.metadata().unwrap(), &added.metadata[..]);
+        let view = ObjectMetadataUpdatedView::from_log(&logs[1]).unwrap();
+        assert_eq!(view.metadata().unwrap(), &added.metadata[..]);
+        let view = ObjectDeletedView::from_log(&logs[2]).unwrap();
+        assert_eq!(view.blob_hash(), &B256::repeat_byte(2));
+    }
+
+    #[test]
+    fn non_utf8_key() {
+        let log = ObjectDeleted {
+            key: Bytes::from_static(&[0xff]),
+            blobHash: B256::ZERO,
+        }
+        .encode_log_data();
+        let view = ObjectDeletedView::from_log(&log).unwrap();
+        assert_eq!(view.key().unwrap(), [0xff]);
+        assert!(view.key_str().is_err());
+    }
+
+    #[test]
+    fn rejects_other_logs() {
+        let logs = logs();
+        assert!(ObjectAddedView::from_log(&logs[1]).is_none());
+        let topics = logs[0].topics();
+        let data = &logs[0].data;
+        assert!(LazyEvent::new(topics, &data[..abi::WORD]).is_none());
+        assert!(LazyEvent::new(&[topics[0], B256::ZERO], data).is_none());
+        assert!(LazyEvent::new(&[B256::ZERO], data).is_none());
+    }
+}
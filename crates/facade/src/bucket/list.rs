@@ -0,0 +1,372 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use alloy_primitives::Address;
+use anyhow::anyhow;
+
+use super::{queryObjects_0Call, Object};
+use crate::executor::CallExecutor;
+
+/// An item of a bucket listing.
+#[derive(Clone)]
+pub enum ListEntry {
+    Object(Object),
+    /// A common prefix, ending with the delimiter.
+    CommonPrefix(String),
+}
+
+// The generated `Object` doesn't implement `Debug`.
+impl fmt::Debug for ListEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Object(object) => {
+                let metadata: Vec<_> = object
+                    .state
+                    .metadata
+                    .iter()
+                    .map(|kv| (&kv.key, &kv.value))
+                    .collect();
+                f.debug_struct("Object")
+                    .field("key", &object.key)
+                    .field("blob_hash", &object.state.blobHash)
+                    .field("size", &object.state.size)
+                    .field("expiry", &object.state.expiry)
+                    .field("metadata", &metadata)
+                    .finish()
+            }
+            Self::CommonPrefix(prefix) => f.debug_tuple("CommonPrefix").field(prefix).finish(),
+        }
+    }
+}
+
+/// Returns a lister for the objects of the bucket at `bucket`.
+pub fn list_objects<E: CallExecutor>(executor: &mut E, bucket: Address) -> ObjectLister<'_, E> {
+    ObjectLister {
+        executor,
+        bucket,
+        prefix: String::new(),
+        delimiter: String::new(),
+        page_size: ObjectLister::<E>::DEFAULT_PAGE_SIZE,
+        next_key: Some(String::new()),
+        visited: HashSet::new(),
+        prefixes: HashSet::new(),
+        buffer: VecDeque::new(),
+        error: None,
+        failed_key: None,
+    }
+}
+
+/// Lists bucket objects by calling `queryObjects(prefix, delimiter, startKey, limit)` page by
+/// page, feeding back `nextKey`.
+///
+/// Yields objects and, if a delimiter is set, each common prefix once. If the facade returns a
+/// `nextKey` that was already queried, the iterator yields an error and stops, rather than
+/// looping forever. If a call fails, the iterator yields the error and stops too; call
+/// [`retry`](Self::retry) to resume from the failed page.
+pub struct ObjectLister<'a, E> {
+    executor: &'a mut E,
+    bucket: Address,
+    prefix: String,
+    delimiter: String,
+    page_size: u64,
+    /// The start key of the next page, or `None` when done.
+    next_key: Option<String>,
+    visited: HashSet<String>,
+    prefixes: HashSet<String>,
+    buffer: VecDeque<ListEntry>,
+    /// An error to yield once the buffer is drained.
+    error: Option<anyhow::Error>,
+    /// The start key of the page whose call failed.
+    failed_key: Option<String>,
+}
+
+impl<E: CallExecutor> ObjectLister<'_, E> {
+    pub const DEFAULT_PAGE_SIZE: u64 = 1000;
+
+    /// Only lists keys starting with `prefix`.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Rolls up keys containing `delimiter` after the prefix into common prefixes.
+    pub fn delimiter(mut self, delimiter: impl Into<String>) -> Self {
+        self.delimiter = delimiter.into();
+        self
+    }
+
+    /// Sets the maximum number of objects per call. Zero lets the facade return everything in
+    /// one call.
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Starts listing at `key`, e.g. a `nextKey` saved from an earlier listing.
+    pub fn start_key(mut self, key: impl Into<String>) -> Self {
+        self.next_key = Some(key.into());
+        self
+    }
+
+    /// Returns the start key of the next page, or `None` when the listing is done or a call
+    /// failed.
+    pub fn next_key(&self) -> Option<&str> {
+        self.next_key.as_deref()
+    }
+
+    /// Resumes the listing from the page whose call failed, so that the next call to `next`
+    /// repeats the call. Returns `false` if no call failed.
+    pub fn retry(&mut self) -> bool {
+        match self.failed_key.take() {
+            Some(key) => {
+                self.next_key = Some(key);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Fetches the next page into the buffer.
+    fn fetch(&mut self, start_key: String) -> anyhow::Result<()> {
+        let call = queryObjects_0Call {
+            prefix: self.prefix.clone(),
+            delimiter: self.delimiter.clone(),
+            startKey: start_key.clone(),
+            limit: self.page_size,
+        };
+        let query = match self.executor.call(self.bucket, &call) {
+            Ok(executed) => executed.ret._0,
+            Err(e) => {
+                // Stop, but keep the cursor for `retry`.
+                self.failed_key = Some(start_key);
+                return Err(e);
+            }
+        };
+        self.visited.insert(start_key);
+        for prefix in query.commonPrefixes {
+            if self.prefixes.insert(prefix.clone()) {
+                self.buffer.push_back(ListEntry::CommonPrefix(prefix));
+            }
+        }
+        self.buffer.extend(query.objects.into_iter().map(ListEntry::Object));
+        if query.nextKey.is_empty() {
+            return Ok(());
+        }
+        if self.visited.contains(&query.nextKey) {
+            self.error = Some(anyhow!(
+                "queryObjects returned nextKey {:?}, which was already queried",
+                query.nextKey
+            ));
+        } else {
+            self.next_key = Some(query.nextKey);
+        }
+        Ok(())
+    }
+}
+
+impl<E: CallExecutor> Iterator for ObjectLister<'_, E> {
+    type Item = anyhow::Result<ListEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.buffer.pop_front() {
+                return Some(Ok(entry));
+            }
+            if let Some(e) = self.error.take() {
+                return Some(Err(e));
+            }
+            let start_key = self.next_key.take()?;
+            if let Err(e) = self.fetch(start_key) {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Bytes, B256};
+    use alloy_sol_types::SolCall;
+    use anyhow::bail;
+
+    use super::*;
+    use crate::bucket::{addObject_1Call, BucketModel, Query};
+    use crate::executor::Execution;
+
+    /// Returns one common prefix per call, named after the start key, and the scripted
+    /// `nextKey`s in order.
+    struct ScriptedExecutor {
+        next_keys: VecDeque<&'static str>,
+        start_keys: Vec<String>,
+    }
+
+    impl CallExecutor for ScriptedExecutor {
+        fn execute(&mut self, _: Address, calldata: Bytes) -> anyhow::Result<Execution> {
+            let call = queryObjects_0Call::abi_decode(&calldata, true)?;
+            self.start_keys.push(call.startKey.clone());
+            let query = Query {
+                objects: Vec::new(),
+                commonPrefixes: vec![format!("{}/", call.startKey)],
+                nextKey: self.next_keys.pop_front().unwrap().to_string(),
+            };
+            Ok(Execution {
+                output: queryObjects_0Call::abi_encode_returns(&(query,)).into(),
+                gas_used: 0,
+            })
+        }
+    }
+
+    /// Fails the first `failures` calls, then delegates to the model.
+    struct FlakyExecutor {
+        model: BucketModel,
+        failures: usize,
+        calls: usize,
+    }
+
+    impl CallExecutor for FlakyExecutor {
+        fn execute(&mut self, to: Address, calldata: Bytes) -> anyhow::Result<Execution> {
+            self.calls += 1;
+            if self.failures > 0 {
+                self.failures -= 1;
+                bail!("connection reset");
+            }
+            self.model.execute(to, calldata)
+        }
+    }
+
+    fn model(keys: &[&str]) -> BucketModel {
+        let mut model = BucketModel::new(100);
+        for key in keys {
+            model
+                .add_object(&addObject_1Call {
+                    source: B256::ZERO,
+                    key: key.to_string(),
+                    hash: B256::repeat_byte(1),
+                    recoveryHash: B256::ZERO,
+                    size: 1,
+                    ttl: 0,
+                    metadata: Vec::new(),
+                    overwrite: false,
+                })
+                .unwrap();
+        }
+        model
+    }
+
+    fn names(entries: Vec<ListEntry>) -> Vec<String> {
+        entries
+            .into_iter()
+            .map(|entry| match entry {
+                ListEntry::Object(object) => object.key,
+                ListEntry::CommonPrefix(prefix) => prefix,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lists_pages_and_prefixes_once() {
+        let mut model = model(&["a/1", "a/2", "b", "c/1", "d"]);
+        let entries = list_objects(&mut model, Address::ZERO)
+            .delimiter("/")
+            .page_size(1)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+        let mut names = names(entries);
+        names.sort();
+        assert_eq!(names, ["a/", "b", "c/", "d"]);
+    }
+
+    #[test]
+    fn stops_when_next_key_does_not_advance() {
+        // The same key as the current page, and an earlier key.
+        let cases = [
+            (vec!["a", "a"], vec!["", "a"]),
+            (vec!["a", "b", "c", "a"], vec!["", "a", "b", "c"]),
+        ];
+        for (next_keys, queried) in cases {
+            let repeated = next_keys.last().unwrap().to_string();
+            let mut executor = ScriptedExecutor {
+                next_keys: next_keys.into(),
+                start_keys: Vec::new(),
+            };
+            let mut lister = list_objects(&mut executor, Address::ZERO);
+            let mut entries = Vec::new();
+            let err = loop {
+                match lister.next().unwrap() {
+                    Ok(entry) => entries.push(entry),
+                    Err(e) => break e,
+                }
+            };
+            assert_eq!(
+                err.to_string(),
+                format!("queryObjects returned nextKey {:?}, which was already queried", repeated)
+            );
+            assert!(lister.next().is_none());
+            assert!(lister.next().is_none());
+            assert_eq!(lister.next_key(), None);
+            assert!(!lister.retry());
+            drop(lister);
+            // The entries of the last page are yielded before the error.
+            let prefixes: Vec<_> = queried.iter().map(|k| format!("{}/", k)).collect();
+            assert_eq!(names(entries), prefixes);
+            assert_eq!(executor.start_keys, queried);
+        }
+    }
+
+    #[test]
+    fn debug_format() {
+        let mut model = model(&["a"]);
+        let entries = list_objects(&mut model, Address::ZERO)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", entries),
+            format!(
+                "[Object {{ key: \"a\", blob_hash: {}, size: 1, expiry: 100, metadata: [] }}]",
+                B256::repeat_byte(1)
+            )
+        );
+        let prefix = ListEntry::CommonPrefix("a/".to_string());
+        assert_eq!(format!("{:?}", prefix), "CommonPrefix(\"a/\")");
+    }
+
+    #[test]
+    fn fuses_after_error() {
+        let mut executor = FlakyExecutor {
+            model: model(&["a", "b"]),
+            failures: 1,
+            calls: 0,
+        };
+        let mut lister = list_objects(&mut executor, Address::ZERO);
+        assert!(lister.next().unwrap().is_err());
+        assert!(lister.next().is_none());
+        assert!(lister.next().is_none());
+        assert_eq!(lister.next_key(), None);
+        drop(lister);
+        assert_eq!(executor.calls, 1);
+    }
+
+    #[test]
+    fn retry_repeats_failed_page() {
+        let mut executor = FlakyExecutor {
+            model: model(&["a", "b", "c"]),
+            failures: 0,
+            calls: 0,
+        };
+        let mut lister = list_objects(&mut executor, Address::ZERO).page_size(2);
+        assert!(!lister.retry());
+        assert!(matches!(lister.next(), Some(Ok(ListEntry::Object(o))) if o.key == "a"));
+        assert!(matches!(lister.next(), Some(Ok(ListEntry::Object(o))) if o.key == "b"));
+        lister.executor.failures = 1;
+        assert!(lister.next().unwrap().is_err());
+        assert!(lister.next().is_none());
+        assert!(lister.retry());
+        assert_eq!(lister.next_key(), Some("c"));
+        let rest = lister.collect::<anyhow::Result<Vec<_>>>().unwrap();
+        assert_eq!(names(rest), ["c"]);
+        assert_eq!(executor.calls, 3);
+    }
+}
@@ -0,0 +1,438 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+use alloy_primitives::{Address, B256};
+use anyhow::{anyhow, bail, Context};
+use serde::{Deserialize, Serialize};
+
+use super::codec::{key_values, metadata_entries};
+use super::{
+    addObject_1Call, getObjectCall, list_objects, KeyValue, ListEntry, Object, ObjectValue,
+};
+use crate::executor::CallExecutor;
+use crate::hash::BlobHash;
+
+const CSV_HEADER: [&str; 6] = ["key", "blobHash", "recoveryHash", "size", "expiry", "metadata"];
+
+/// An object in a bucket manifest. Hashes serialize in [`BlobHash`] base32 form, and also parse
+/// from hex.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    pub key: String,
+    pub blob_hash: BlobHash,
+    /// Zero if the entry was built from an [`Object`], which doesn't carry it.
+    pub recovery_hash: BlobHash,
+    pub size: u64,
+    /// The epoch at which the object expires.
+    pub expiry: u64,
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl ManifestEntry {
+    pub fn from_value(key: impl Into<String>, value: &ObjectValue) -> Self {
+        Self {
+            key: key.into(),
+            blob_hash: value.blobHash.into(),
+            recovery_hash: value.recoveryHash.into(),
+            size: value.size,
+            expiry: value.expiry,
+            metadata: metadata_entries(&value.metadata),
+        }
+    }
+
+    pub fn metadata_key_values(&self) -> Vec<KeyValue> {
+        key_values(&self.metadata)
+    }
+}
+
+impl From<&Object> for ManifestEntry {
+    fn from(object: &Object) -> Self {
+        Self {
+            key: object.key.clone(),
+            blob_hash: object.state.blobHash.into(),
+            recovery_hash: BlobHash::default(),
+            size: object.state.size,
+            expiry: object.state.expiry,
+            metadata: metadata_entries(&object.state.metadata),
+        }
+    }
+}
+
+/// Snapshots the objects of the bucket at `bucket` whose keys start with `prefix`, sorted by key.
+///
+/// Lists the objects, then calls `getObject` for each one to get its recovery hash.
+pub fn export_manifest<E: CallExecutor>(
+    executor: &mut E,
+    bucket: Address,
+    prefix: &str,
+) -> anyhow::Result<Vec<ManifestEntry>> {
+    let mut keys = Vec::new();
+    for entry in list_objects(executor, bucket).prefix(prefix) {
+        if let ListEntry::Object(object) = entry? {
+            keys.push(object.key);
+        }
+    }
+    keys.sort();
+    let mut entries = Vec::with_capacity(keys.len());
+    for key in keys {
+        let call = getObjectCall { key: key.clone() };
+        let value = executor
+            .call(bucket, &call)
+            .with_context(|| format!("failed to get object {:?}", key))?
+            .ret
+            ._0;
+        // The object may have been deleted since it was listed.
+        if value.blobHash.is_zero() {
+            continue;
+        }
+        entries.push(ManifestEntry::from_value(key, &value));
+    }
+    Ok(entries)
+}
+
+/// The file format of a manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ManifestFormat {
+    /// One JSON object per line.
+    JsonLines,
+    /// A header row, then one row per entry. Metadata is a JSON object.
+    Csv,
+}
+
+impl FromStr for ManifestFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "jsonl" | "json-lines" | "ndjson" => Ok(Self::JsonLines),
+            "csv" => Ok(Self::Csv),
+            _ => bail!("unknown manifest format {:?}, expected jsonl or csv", s),
+        }
+    }
+}
+
+impl fmt::Display for ManifestFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::JsonLines => f.write_str("jsonl"),
+            Self::Csv => f.write_str("csv"),
+        }
+    }
+}
+
+/// Writes entries in the given format. Output is stable: equal entries in equal order give equal
+/// bytes.
+pub fn write_manifest<W: Write>(
+    mut writer: W,
+    format: ManifestFormat,
+    entries: &[ManifestEntry],
+) -> anyhow::Result<()> {
+    match format {
+        ManifestFormat::JsonLines => {
+            for entry in entries {
+                serde_json::to_writer(&mut writer, entry)?;
+                writer.write_all(b"\n")?;
+            }
+        }
+        ManifestFormat::Csv => {
+            writeln!(writer, "{}", CSV_HEADER.join(","))?;
+            for entry in entries {
+                let row = [
+                    entry.key.clone(),
+                    entry.blob_hash.to_string(),
+                    entry.recovery_hash.to_string(),
+                    entry.size.to_string(),
+                    entry.expiry.to_string(),
+                    serde_json::to_string(&entry.metadata)?,
+                ];
+                let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+                writeln!(writer, "{}", row.join(","))?;
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads entries written by [`write_manifest`]. Blank JSON lines are skipped.
+pub fn read_manifest<R: BufRead>(
+    mut reader: R,
+    format: ManifestFormat,
+) -> anyhow::Result<Vec<ManifestEntry>> {
+    match format {
+        ManifestFormat::JsonLines => {
+            let mut entries = Vec::new();
+            for (i, line) in reader.lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let entry = serde_json::from_str(&line)
+                    .with_context(|| format!("invalid manifest entry on line {}", i + 1))?;
+                entries.push(entry);
+            }
+            Ok(entries)
+        }
+        ManifestFormat::Csv => {
+            let mut input = String::new();
+            reader.read_to_string(&mut input)?;
+            let mut records = parse_csv(&input)?.into_iter();
+            match records.next() {
+                Some(header) if header == CSV_HEADER => {}
+                Some(header) => bail!("unexpected manifest header {:?}", header.join(",")),
+                None => return Ok(Vec::new()),
+            }
+            records
+                .enumerate()
+                .map(|(i, record)| {
+                    csv_entry(record).with_context(|| format!("invalid manifest row {}", i + 1))
+                })
+                .collect()
+        }
+    }
+}
+
+fn csv_entry(record: Vec<String>) -> anyhow::Result<ManifestEntry> {
+    let [key, blob_hash, recovery_hash, size, expiry, metadata]: [String; 6] = record
+        .try_into()
+        .map_err(|r: Vec<String>| anyhow!("expected 6 fields, found {}", r.len()))?;
+    Ok(ManifestEntry {
+        key,
+        blob_hash: blob_hash.parse().context("invalid blobHash")?,
+        recovery_hash: recovery_hash.parse().context("invalid recoveryHash")?,
+        size: size.parse().context("invalid size")?,
+        expiry: expiry.parse().context("invalid expiry")?,
+        metadata: serde_json::from_str(&metadata).context("invalid metadata")?,
+    })
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Parses RFC 4180 CSV, allowing line breaks in quoted fields.
+fn parse_csv(input: &str) -> anyhow::Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut chars = input.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if quoted {
+        bail!("unterminated quoted field");
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Turns manifest entries into `addObject` calls, e.g. to copy objects into another bucket.
+#[derive(Clone, Debug)]
+pub struct ManifestImporter {
+    source: B256,
+    epoch: Option<u64>,
+    overwrite: bool,
+}
+
+impl ManifestImporter {
+    /// Returns an importer for blobs available from the node with ID `source`.
+    pub fn new(source: B256) -> Self {
+        Self {
+            source,
+            epoch: None,
+            overwrite: false,
+        }
+    }
+
+    /// Keeps each entry's expiry by setting `ttl` to `expiry - epoch`, where `epoch` is the
+    /// current epoch. Entries that expired by then are rejected. Without this, objects get the
+    /// bucket's default TTL.
+    pub fn keep_expiry(mut self, epoch: u64) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
+    /// Sets `overwrite` on the calls, replacing existing objects. Off by default.
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    pub fn calls(&self, entries: &[ManifestEntry]) -> anyhow::Result<Vec<addObject_1Call>> {
+        entries.iter().map(|entry| self.call(entry)).collect()
+    }
+
+    pub fn call(&self, entry: &ManifestEntry) -> anyhow::Result<addObject_1Call> {
+        let ttl = match self.epoch {
+            Some(epoch) if entry.expiry <= epoch => bail!(
+                "object {:?} expired at epoch {}, which is not after epoch {}",
+                entry.key,
+                entry.expiry,
+                epoch
+            ),
+            Some(epoch) => entry.expiry - epoch,
+            None => 0,
+        };
+        Ok(addObject_1Call {
+            source: self.source,
+            key: entry.key.clone(),
+            hash: entry.blob_hash.into(),
+            recoveryHash: entry.recovery_hash.into(),
+            size: entry.size,
+            ttl,
+            metadata: entry.metadata_key_values(),
+            overwrite: self.overwrite,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<ManifestEntry> {
+        vec![
+            ManifestEntry {
+                key: "plain.txt".to_string(),
+                blob_hash: B256::repeat_byte(1).into(),
+                recovery_hash: BlobHash::default(),
+                size: 10,
+                expiry: 100,
+                metadata: BTreeMap::new(),
+            },
+            ManifestEntry {
+                key: "a, \"quoted\"\nkey".to_string(),
+                blob_hash: B256::repeat_byte(2).into(),
+                recovery_hash: B256::repeat_byte(3).into(),
+                size: 20,
+                expiry: 200,
+                metadata: BTreeMap::from([
+                    ("note".to_string(), "x, \"y\"\r\nz".to_string()),
+                    ("type".to_string(), "text/plain".to_string()),
+                ]),
+            },
+        ]
+    }
+
+    fn round_trip(format: ManifestFormat) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_manifest(&mut out, format, &entries()).unwrap();
+        assert_eq!(read_manifest(&out[..], format).unwrap(), entries());
+        out
+    }
+
+    #[test]
+    fn json_lines_round_trip() {
+        let out = String::from_utf8(round_trip(ManifestFormat::JsonLines)).unwrap();
+        assert_eq!(out.lines().count(), 2);
+        let hash = BlobHash::new(B256::repeat_byte(1)).to_base32();
+        assert!(out.starts_with(&format!("{{\"key\":\"plain.txt\",\"blobHash\":\"{}\"", hash)));
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let out = String::from_utf8(round_trip(ManifestFormat::Csv)).unwrap();
+        let first = out.lines().nth(1).unwrap();
+        assert_eq!(
+            first,
+            format!(
+                "plain.txt,{},{},10,100,{{}}",
+                BlobHash::new(B256::repeat_byte(1)),
+                BlobHash::default()
+            )
+        );
+    }
+
+    #[test]
+    fn reads_hex_hashes() {
+        let input = format!(
+            "{}\nk,{},{},1,2,{{}}\n",
+            CSV_HEADER.join(","),
+            B256::repeat_byte(1),
+            B256::ZERO
+        );
+        let entries = read_manifest(input.as_bytes(), ManifestFormat::Csv).unwrap();
+        assert_eq!(entries[0].blob_hash, BlobHash::new(B256::repeat_byte(1)));
+    }
+
+    #[test]
+    fn parses_quoted_fields() {
+        let input = "a,\"b,c\",\"say \"\"hi\"\"\"\r\n\"multi\nline\",,\"\"\nlast";
+        assert_eq!(
+            parse_csv(input).unwrap(),
+            [
+                vec!["a", "b,c", "say \"hi\""],
+                vec!["multi\nline", "", ""],
+                vec!["last"],
+            ]
+        );
+        assert!(parse_csv("a,\"unterminated\n").is_err());
+        assert!(parse_csv("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn quotes_only_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+
+    #[test]
+    fn rejects_malformed_csv() {
+        let header = CSV_HEADER.join(",");
+        let bad = [
+            "key,hash\n".to_string(),
+            format!("{}\nk,zz,zz,1,2,{{}}\n", header),
+            format!("{}\nk,1,2\n", header),
+        ];
+        for input in bad {
+            assert!(read_manifest(input.as_bytes(), ManifestFormat::Csv).is_err(), "{}", input);
+        }
+        assert!(read_manifest(&b""[..], ManifestFormat::Csv).unwrap().is_empty());
+    }
+
+    #[test]
+    fn importer_keeps_expiry() {
+        let entries = entries();
+        let importer = ManifestImporter::new(B256::ZERO).keep_expiry(150).overwrite(true);
+        assert!(importer.call(&entries[0]).is_err());
+        let call = importer.call(&entries[1]).unwrap();
+        assert_eq!(call.ttl, 50);
+        assert_eq!(call.hash, B256::repeat_byte(2));
+        assert_eq!(call.recoveryHash, B256::repeat_byte(3));
+        assert_eq!(call.metadata.len(), 2);
+        assert!(call.overwrite);
+        assert_eq!(ManifestImporter::new(B256::ZERO).call(&entries[0]).unwrap().ttl, 0);
+    }
+}
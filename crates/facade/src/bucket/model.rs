@@ -0,0 +1,439 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
+
+use alloy_primitives::{Address, Bytes, B256};
+use alloy_sol_types::{SolCall, SolInterface};
+use anyhow::{anyhow, bail};
+
+use super::codec::{key_values, metadata_entries};
+use super::{
+    addObject_0Call, addObject_1Call, deleteObjectCall, getObjectCall, queryObjects_0Call,
+    queryObjects_1Call, queryObjects_2Call, queryObjects_3Call, queryObjects_4Call,
+    updateObjectMetadataCall, Calls, KeyValue, Object, ObjectState, ObjectValue, Query,
+};
+use crate::executor::{CallExecutor, Execution, ExecutionError};
+
+/// The delimiter used by the `queryObjects` overloads that don't take one.
+pub const DEFAULT_DELIMITER: &str = "/";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct StoredObject {
+    hash: B256,
+    recovery_hash: B256,
+    size: u64,
+    expiry: u64,
+    metadata: BTreeMap<String, String>,
+}
+
+impl StoredObject {
+    fn metadata(&self) -> Vec<KeyValue> {
+        key_values(&self.metadata)
+    }
+}
+
+/// An in-memory bucket with the semantics of the bucket facade.
+///
+/// Useful as a test double: it implements [`CallExecutor`], so code written against a real
+/// bucket can run against the model unchanged. The semantics are:
+///
+/// - `addObject` fails if the key exists, unless `overwrite` is set. The object expires `ttl`
+///   epochs after the current [`epoch`](Self::set_epoch), or after the default TTL if `ttl` is
+///   zero.
+/// - `deleteObject` and `updateObjectMetadata` fail if the key doesn't exist. Metadata entries
+///   with an empty value are removed, others are inserted or replaced. The resulting metadata is
+///   what the `ObjectMetadataUpdated` event carries.
+/// - `getObject` returns a zeroed `ObjectValue` if the key doesn't exist.
+/// - `queryObjects` visits keys from `startKey` (inclusive), skipping keys without `prefix`.
+///   If `delimiter` is not empty and occurs in a key after the prefix, the key is rolled up into
+///   a common prefix ending with the delimiter instead of being returned. At most `limit`
+///   objects are returned, with zero meaning no limit; rolled-up keys don't count. `nextKey`
+///   is the key after the last visited one, or empty when done. Overloads without a delimiter
+///   use [`DEFAULT_DELIMITER`].
+///
+/// The actor visits keys in the order of its HAMT, while the model visits them in
+/// lexicographic byte order. Both return every key exactly once when paging with `nextKey`,
+/// but pages may be split differently.
+#[derive(Clone, Debug)]
+pub struct BucketModel {
+    objects: BTreeMap<String, StoredObject>,
+    epoch: u64,
+    default_ttl: u64,
+}
+
+impl BucketModel {
+    pub fn new(default_ttl: u64) -> Self {
+        Self {
+            objects: BTreeMap::new(),
+            epoch: 0,
+            default_ttl,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Sets the current epoch, used to compute expiries of added objects.
+    pub fn set_epoch(&mut self, epoch: u64) {
+        self.epoch = epoch;
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    pub fn add_object(&mut self, call: &addObject_1Call) -> anyhow::Result<()> {
+        if !call.overwrite && self.objects.contains_key(&call.key) {
+            bail!("key {:?} already exists; set overwrite to replace it", call.key);
+        }
+        let ttl = if call.ttl == 0 {
+            self.default_ttl
+        } else {
+            call.ttl
+        };
+        let metadata = metadata_entries(&call.metadata);
+        self.objects.insert(
+            call.key.clone(),
+            StoredObject {
+                hash: call.hash,
+                recovery_hash: call.recoveryHash,
+                size: call.size,
+                expiry: self.epoch.saturating_add(ttl),
+                metadata,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn delete_object(&mut self, key: &str) -> anyhow::Result<()> {
+        self.objects
+            .remove(key)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("key {:?} not found", key))
+    }
+
+    pub fn update_object_metadata(
+        &mut self,
+        key: &str,
+        metadata: &[KeyValue],
+    ) -> anyhow::Result<()> {
+        let object = self
+            .objects
+            .get_mut(key)
+            .ok_or_else(|| anyhow!("key {:?} not found", key))?;
+        for kv in metadata {
+            if kv.value.is_empty() {
+                object.metadata.remove(&kv.key);
+            } else {
+                object.metadata.insert(kv.key.clone(), kv.value.clone());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_object(&self, key: &str) -> Option<ObjectValue> {
+        self.objects.get(key).map(|o| ObjectValue {
+            blobHash: o.hash,
+            recoveryHash: o.recovery_hash,
+            size: o.size,
+            expiry: o.expiry,
+            metadata: o.metadata(),
+        })
+    }
+
+    pub fn query_objects(
+        &self,
+        prefix: &str,
+        delimiter: &str,
+        start_key: &str,
+        limit: u64,
+    ) -> Query {
+        query(&self.objects, prefix, delimiter, start_key, limit, |o| ObjectState {
+            blobHash: o.hash,
+            size: o.size,
+            expiry: o.expiry,
+            metadata: o.metadata(),
+        })
+    }
+
+    /// Executes a decoded bucket facade call and returns the ABI-encoded return data.
+    pub fn call(&mut self, call: &Calls) -> anyhow::Result<Vec<u8>> {
+        let output = match call {
+            Calls::addObject_0(c) => {
+                self.add_object(&addObject_1Call {
+                    source: c.source,
+                    key: c.key.clone(),
+                    hash: c.hash,
+                    recoveryHash: c.recoveryHash,
+                    size: c.size,
+                    ttl: 0,
+                    metadata: Vec::new(),
+                    overwrite: false,
+                })?;
+                addObject_0Call::abi_encode_returns(&())
+            }
+            Calls::addObject_1(c) => {
+                self.add_object(c)?;
+                addObject_1Call::abi_encode_returns(&())
+            }
+            Calls::deleteObject(c) => {
+                self.delete_object(&c.key)?;
+                deleteObjectCall::abi_encode_returns(&())
+            }
+            Calls::updateObjectMetadata(c) => {
+                self.update_object_metadata(&c.key, &c.metadata)?;
+                updateObjectMetadataCall::abi_encode_returns(&())
+            }
+            Calls::getObject(c) => {
+                let value = self.get_object(&c.key).unwrap_or_else(|| ObjectValue {
+                    blobHash: B256::ZERO,
+                    recoveryHash: B256::ZERO,
+                    size: 0,
+                    expiry: 0,
+                    metadata: Vec::new(),
+                });
+                getObjectCall::abi_encode_returns(&(value,))
+            }
+            Calls::queryObjects_0(c) => {
+                let query = self.query_objects(&c.prefix, &c.delimiter, &c.startKey, c.limit);
+                queryObjects_0Call::abi_encode_returns(&(query,))
+            }
+            Calls::queryObjects_1(c) => {
+                let query = self.query_objects(&c.prefix, &c.delimiter, &c.startKey, 0);
+                queryObjects_1Call::abi_encode_returns(&(query,))
+            }
+            Calls::queryObjects_2(c) => {
+                let query = self.query_objects(&c.prefix, DEFAULT_DELIMITER, "", 0);
+                queryObjects_2Call::abi_encode_returns(&(query,))
+            }
+            Calls::queryObjects_3(_) => {
+                let query = self.query_objects("", DEFAULT_DELIMITER, "", 0);
+                queryObjects_3Call::abi_encode_returns(&(query,))
+            }
+            Calls::queryObjects_4(c) => {
+                let query = self.query_objects(&c.prefix, &c.delimiter, "", 0);
+                queryObjects_4Call::abi_encode_returns(&(query,))
+            }
+        };
+        Ok(output)
+    }
+}
+
+/// Runs `queryObjects` over `objects` with the semantics described on [`BucketModel`].
+pub(crate) fn query<T>(
+    objects: &BTreeMap<String, T>,
+    prefix: &str,
+    delimiter: &str,
+    start_key: &str,
+    limit: u64,
+    state: impl Fn(&T) -> ObjectState,
+) -> Query {
+    let mut found = Vec::new();
+    let mut common_prefixes = BTreeSet::new();
+    let mut range = objects
+        .range::<str, _>((Bound::Included(start_key), Bound::Unbounded))
+        .peekable();
+    for (key, object) in range.by_ref() {
+        if !key.starts_with(prefix) {
+            continue;
+        }
+        let rest = &key[prefix.len()..];
+        match rest.find(delimiter).filter(|_| !delimiter.is_empty()) {
+            Some(index) => {
+                let end = prefix.len() + index + delimiter.len();
+                common_prefixes.insert(key[..end].to_string());
+            }
+            None => {
+                found.push(Object {
+                    key: key.clone(),
+                    state: state(object),
+                });
+                if limit != 0 && found.len() as u64 >= limit {
+                    break;
+                }
+            }
+        }
+    }
+    Query {
+        objects: found,
+        commonPrefixes: common_prefixes.into_iter().collect(),
+        nextKey: range.peek().map(|(k, _)| k.to_string()).unwrap_or_default(),
+    }
+}
+
+impl CallExecutor for BucketModel {
+    /// Executes `calldata` against the model. The target address is ignored and no gas is used.
+    /// Failing calls revert, without revert data.
+    fn execute(&mut self, _to: Address, calldata: Bytes) -> anyhow::Result<Execution> {
+        let call = Calls::abi_decode(&calldata, true)?;
+        let output = self.call(&call).map_err(|e| {
+            anyhow::Error::new(ExecutionError::Reverted(Bytes::new())).context(e.to_string())
+        })?;
+        Ok(Execution {
+            output: output.into(),
+            gas_used: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_call(key: &str, size: u64) -> addObject_1Call {
+        addObject_1Call {
+            source: B256::ZERO,
+            key: key.to_string(),
+            hash: B256::repeat_byte(size as u8),
+            recoveryHash: B256::ZERO,
+            size,
+            ttl: 0,
+            metadata: vec![KeyValue {
+                key: "k".to_string(),
+                value: "v".to_string(),
+            }],
+            overwrite: false,
+        }
+    }
+
+    fn model(keys: &[&str]) -> BucketModel {
+        let mut model = BucketModel::new(100);
+        for key in keys {
+            model.add_object(&add_call(key, 1)).unwrap();
+        }
+        model
+    }
+
+    fn keys(query: &Query) -> Vec<&str> {
+        query.objects.iter().map(|o| o.key.as_str()).collect()
+    }
+
+    #[test]
+    fn rolls_up_keys_after_prefix() {
+        let model = model(&["a/b/1", "a/b/2", "a/c", "a/d/1", "b/1"]);
+        let query = model.query_objects("a/", "/", "", 0);
+        assert_eq!(keys(&query), ["a/c"]);
+        assert_eq!(query.commonPrefixes, ["a/b/", "a/d/"]);
+        assert_eq!(query.nextKey, "");
+
+        let query = model.query_objects("", "/", "", 0);
+        assert!(query.objects.is_empty());
+        assert_eq!(query.commonPrefixes, ["a/", "b/"]);
+
+        let query = model.query_objects("a/", "", "", 0);
+        assert_eq!(keys(&query), ["a/b/1", "a/b/2", "a/c", "a/d/1"]);
+        assert!(query.commonPrefixes.is_empty());
+    }
+
+    #[test]
+    fn limit_counts_only_objects() {
+        let model = model(&["a/1", "a/2", "b", "c/1", "d", "e"]);
+        let query = model.query_objects("", "/", "", 2);
+        assert_eq!(keys(&query), ["b", "d"]);
+        assert_eq!(query.commonPrefixes, ["a/", "c/"]);
+        assert_eq!(query.nextKey, "e");
+
+        let query = model.query_objects("", "/", "", 3);
+        assert_eq!(keys(&query), ["b", "d", "e"]);
+        assert_eq!(query.nextKey, "");
+    }
+
+    #[test]
+    fn pages_across_common_prefixes() {
+        let model = model(&["a/1", "a/2", "b", "c/1", "c/2", "d", "e/1"]);
+        let (mut objects, mut prefixes) = (Vec::new(), BTreeSet::new());
+        let mut start_key = String::new();
+        let mut pages = 0;
+        loop {
+            let query = model.query_objects("", "/", &start_key, 1);
+            objects.extend(keys(&query).into_iter().map(str::to_string));
+            prefixes.extend(query.commonPrefixes);
+            pages += 1;
+            if query.nextKey.is_empty() {
+                break;
+            }
+            assert!(query.nextKey > start_key);
+            start_key = query.nextKey;
+        }
+        assert_eq!(objects, ["b", "d"]);
+        assert_eq!(prefixes.into_iter().collect::<Vec<_>>(), ["a/", "c/", "e/"]);
+        assert_eq!(pages, 3);
+    }
+
+    #[test]
+    fn start_key_is_inclusive() {
+        let model = model(&["a", "b", "c"]);
+        let query = model.query_objects("", "", "b", 0);
+        assert_eq!(keys(&query), ["b", "c"]);
+    }
+
+    #[test]
+    fn overwrite_and_delete() {
+        let mut model = BucketModel::new(100);
+        model.set_epoch(10);
+        model.add_object(&add_call("a", 1)).unwrap();
+        assert!(model.add_object(&add_call("a", 2)).is_err());
+        assert_eq!(model.get_object("a").unwrap().size, 1);
+
+        let mut call = add_call("a", 2);
+        call.overwrite = true;
+        call.ttl = 5;
+        model.add_object(&call).unwrap();
+        let value = model.get_object("a").unwrap();
+        assert_eq!((value.size, value.expiry), (2, 15));
+        assert_eq!(model.len(), 1);
+
+        model.delete_object("a").unwrap();
+        assert!(model.get_object("a").is_none());
+        assert!(model.delete_object("a").is_err());
+        assert!(model.is_empty());
+    }
+
+    #[test]
+    fn default_ttl_applies_to_zero_ttl() {
+        let mut model = BucketModel::new(100);
+        model.set_epoch(7);
+        model.add_object(&add_call("a", 1)).unwrap();
+        assert_eq!(model.get_object("a").unwrap().expiry, 107);
+    }
+
+    #[test]
+    fn executes_encoded_calls() {
+        let mut model = model(&["a/1", "b"]);
+        let query = CallExecutor::call(
+            &mut model,
+            Address::ZERO,
+            &queryObjects_2Call {
+                prefix: String::new(),
+            },
+        )
+        .unwrap()
+        .ret
+        ._0;
+        assert_eq!(keys(&query), ["b"]);
+        assert_eq!(query.commonPrefixes, ["a/"]);
+
+        let missing = CallExecutor::call(
+            &mut model,
+            Address::ZERO,
+            &getObjectCall {
+                key: "c".to_string(),
+            },
+        )
+        .unwrap()
+        .ret
+        ._0;
+        assert!(missing.blobHash.is_zero());
+
+        let err = CallExecutor::call(&mut model, Address::ZERO, &add_call("b", 1)).err().unwrap();
+        assert_eq!(err.to_string(), "key \"b\" already exists; set overwrite to replace it");
+        assert_eq!(ExecutionError::find(&err), Some(&ExecutionError::Reverted(Bytes::new())));
+    }
+}
@@ -0,0 +1,441 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+
+use super::{KeyValue, ListEntry, Object, ObjectLister};
+use crate::executor::CallExecutor;
+
+/// A numeric comparison operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompareOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+}
+
+/// A predicate over one metadata key.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Predicate {
+    /// `key`: the key is present.
+    Exists(String),
+    /// `!key`: the key is absent.
+    Missing(String),
+    /// `key=value`
+    Equals(String, String),
+    /// `key!=value`: the key is absent or has another value.
+    NotEquals(String, String),
+    /// `key^=value`: the value starts with the given prefix.
+    Prefix(String, String),
+    /// `key<n`, `key<=n`, `key>n` or `key>=n`: the value parses as a number and compares as
+    /// given. Values that aren't numbers never match.
+    Compare(String, CompareOp, f64),
+}
+
+impl Predicate {
+    pub fn matches(&self, metadata: &[KeyValue]) -> bool {
+        let value = |key: &str| metadata.iter().find(|kv| kv.key == key).map(|kv| &kv.value);
+        match self {
+            Self::Exists(key) => value(key).is_some(),
+            Self::Missing(key) => value(key).is_none(),
+            Self::Equals(key, expected) => value(key) == Some(expected),
+            Self::NotEquals(key, expected) => value(key) != Some(expected),
+            Self::Prefix(key, prefix) => value(key).is_some_and(|v| v.starts_with(prefix.as_str())),
+            Self::Compare(key, op, rhs) => {
+                let Some(lhs) = value(key).and_then(|v| v.trim().parse::<f64>().ok()) else {
+                    return false;
+                };
+                match op {
+                    CompareOp::Lt => lhs < *rhs,
+                    CompareOp::Le => lhs <= *rhs,
+                    CompareOp::Gt => lhs > *rhs,
+                    CompareOp::Ge => lhs >= *rhs,
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exists(key) => write!(f, "{}", key),
+            Self::Missing(key) => write!(f, "!{}", key),
+            Self::Equals(key, value) => write!(f, "{}={}", key, Quoted(value)),
+            Self::NotEquals(key, value) => write!(f, "{}!={}", key, Quoted(value)),
+            Self::Prefix(key, value) => write!(f, "{}^={}", key, Quoted(value)),
+            Self::Compare(key, op, n) => write!(f, "{}{}{}", key, op.as_str(), n),
+        }
+    }
+}
+
+/// A conjunction of [`Predicate`]s over `ObjectState.metadata`.
+///
+/// Parses from comma-separated predicates, e.g. `content-type^=image/,owner=alice,size>=1024`.
+/// Values containing commas or surrounding spaces can be double-quoted, but no value can contain
+/// a double quote. Keys can't contain `=`, `!`, `^`, `<`, `>`, `,` or `"`. An empty filter
+/// matches everything.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MetadataFilter {
+    pub predicates: Vec<Predicate>,
+}
+
+impl MetadataFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a predicate that must also hold.
+    pub fn and(mut self, predicate: Predicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    pub fn matches(&self, metadata: &[KeyValue]) -> bool {
+        self.predicates.iter().all(|p| p.matches(metadata))
+    }
+
+    pub fn matches_object(&self, object: &Object) -> bool {
+        self.matches(&object.state.metadata)
+    }
+}
+
+impl FromStr for MetadataFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut filter = Self::new();
+        for clause in split_clauses(s)? {
+            if !clause.trim().is_empty() {
+                filter.predicates.push(parse_predicate(clause)?);
+            }
+        }
+        Ok(filter)
+    }
+}
+
+impl fmt::Display for MetadataFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, predicate) in self.predicates.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", predicate)?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits on commas outside double quotes.
+fn split_clauses(s: &str) -> anyhow::Result<Vec<&str>> {
+    let mut clauses = Vec::new();
+    let (mut start, mut quoted) = (0, false);
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                clauses.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if quoted {
+        bail!("unterminated quote in metadata filter {:?}", s);
+    }
+    clauses.push(&s[start..]);
+    Ok(clauses)
+}
+
+fn parse_predicate(clause: &str) -> anyhow::Result<Predicate> {
+    let clause = clause.trim();
+    let Some(at) = clause.find(['=', '!', '^', '<', '>']) else {
+        return Ok(Predicate::Exists(parse_key(clause, clause)?));
+    };
+    if at == 0 && clause.starts_with('!') {
+        return Ok(Predicate::Missing(parse_key(&clause[1..], clause)?));
+    }
+    let key = parse_key(&clause[..at], clause)?;
+    let rest = &clause[at..];
+    let (op, value) = ["!=", "^=", "<=", ">=", "=", "<", ">"]
+        .into_iter()
+        .find_map(|op| rest.strip_prefix(op).map(|value| (op, unquote(value.trim()))))
+        .ok_or_else(|| anyhow!("invalid operator in metadata predicate {:?}", clause))?;
+    let number = || match value.parse::<f64>() {
+        Ok(n) if n.is_finite() => Ok(n),
+        _ => Err(anyhow!("{:?} is not a number in metadata predicate {:?}", value, clause)),
+    };
+    Ok(match op {
+        "!=" => Predicate::NotEquals(key, value.to_string()),
+        "^=" => Predicate::Prefix(key, value.to_string()),
+        "=" => Predicate::Equals(key, value.to_string()),
+        "<" => Predicate::Compare(key, CompareOp::Lt, number()?),
+        "<=" => Predicate::Compare(key, CompareOp::Le, number()?),
+        ">" => Predicate::Compare(key, CompareOp::Gt, number()?),
+        _ => Predicate::Compare(key, CompareOp::Ge, number()?),
+    })
+}
+
+fn parse_key(key: &str, clause: &str) -> anyhow::Result<String> {
+    let key = key.trim();
+    if key.is_empty() || key.contains(['=', '!', '^', '<', '>', '"']) {
+        bail!("invalid key in metadata predicate {:?}", clause);
+    }
+    Ok(key.to_string())
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// Formats a value, quoting it if it wouldn't parse back as is.
+struct Quoted<'a>(&'a str);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.contains(',') || self.0.trim() != self.0 {
+            write!(f, "\"{}\"", self.0)
+        } else {
+            f.write_str(self.0)
+        }
+    }
+}
+
+/// The objects of an [`ObjectLister`] that match a [`MetadataFilter`].
+pub struct MatchingObjects<'a, E> {
+    lister: ObjectLister<'a, E>,
+    filter: MetadataFilter,
+}
+
+impl<'a, E: CallExecutor> ObjectLister<'a, E> {
+    /// Only yields objects whose metadata matches `filter`, fetching pages as needed. Common
+    /// prefixes are skipped.
+    pub fn matching(self, filter: MetadataFilter) -> MatchingObjects<'a, E> {
+        MatchingObjects {
+            lister: self,
+            filter,
+        }
+    }
+}
+
+impl<E: CallExecutor> MatchingObjects<'_, E> {
+    /// Returns the start key of the next page, or `None` when the listing is done.
+    pub fn next_key(&self) -> Option<&str> {
+        self.lister.next_key()
+    }
+}
+
+impl<E: CallExecutor> Iterator for MatchingObjects<'_, E> {
+    type Item = anyhow::Result<Object>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.lister.next()? {
+                Ok(ListEntry::Object(object)) if self.filter.matches_object(&object) => {
+                    return Some(Ok(object));
+                }
+                Ok(_) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, B256};
+
+    use super::*;
+    use crate::bucket::{addObject_1Call, list_objects, BucketModel};
+
+    fn metadata(pairs: &[(&str, &str)]) -> Vec<KeyValue> {
+        pairs
+            .iter()
+            .map(|(key, value)| KeyValue {
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+            .collect()
+    }
+
+    fn parse(s: &str) -> Vec<Predicate> {
+        s.parse::<MetadataFilter>().unwrap().predicates
+    }
+
+    fn parse_err(s: &str) -> String {
+        s.parse::<MetadataFilter>().unwrap_err().to_string()
+    }
+
+    fn key_value(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn parses_each_operator() {
+        use Predicate::*;
+
+        let (k, v) = key_value("k", "v");
+        assert_eq!(parse("k"), [Exists(k.clone())]);
+        assert_eq!(parse("!k"), [Missing(k.clone())]);
+        assert_eq!(parse("k=v"), [Equals(k.clone(), v.clone())]);
+        assert_eq!(parse("k!=v"), [NotEquals(k.clone(), v.clone())]);
+        assert_eq!(parse("k^=v"), [Prefix(k.clone(), v)]);
+        assert_eq!(parse("k<1"), [Compare(k.clone(), CompareOp::Lt, 1.0)]);
+        assert_eq!(parse("k<=1.5"), [Compare(k.clone(), CompareOp::Le, 1.5)]);
+        assert_eq!(parse("k>-2"), [Compare(k.clone(), CompareOp::Gt, -2.0)]);
+        assert_eq!(parse("k>=1e3"), [Compare(k, CompareOp::Ge, 1000.0)]);
+    }
+
+    #[test]
+    fn parses_conjunctions_and_whitespace() {
+        use Predicate::*;
+
+        let (owner, alice) = key_value("owner", "alice");
+        assert_eq!(
+            parse(" content-type ^= image/ , owner=alice,, size >= 1024 "),
+            [
+                Prefix("content-type".to_string(), "image/".to_string()),
+                Equals(owner, alice),
+                Compare("size".to_string(), CompareOp::Ge, 1024.0),
+            ]
+        );
+        assert_eq!(parse(""), []);
+        assert_eq!(parse(" , "), []);
+        let (k, empty) = key_value("k", "");
+        assert_eq!(parse("k="), [Equals(k, empty)]);
+    }
+
+    #[test]
+    fn parses_quoted_values() {
+        use Predicate::*;
+
+        let (k, v) = key_value("k", "a,b");
+        assert_eq!(parse("k=\"a,b\",j"), [Equals(k, v), Exists("j".to_string())]);
+        let (k, v) = key_value("k", " padded ");
+        assert_eq!(parse("k!=\" padded \""), [NotEquals(k, v)]);
+        // Only fully quoted values are unquoted.
+        let (k, v) = key_value("k", "\"a\"b");
+        assert_eq!(parse("k=\"a\"b"), [Equals(k, v)]);
+    }
+
+    #[test]
+    fn rejects_malformed_clauses() {
+        assert_eq!(
+            parse_err("k=\"a,b"),
+            "unterminated quote in metadata filter \"k=\\\"a,b\""
+        );
+        assert_eq!(parse_err("=v"), "invalid key in metadata predicate \"=v\"");
+        assert_eq!(parse_err("!"), "invalid key in metadata predicate \"!\"");
+        assert_eq!(parse_err("!k=v"), "invalid key in metadata predicate \"!k=v\"");
+        assert_eq!(parse_err("\"k\"=v"), "invalid key in metadata predicate \"\\\"k\\\"=v\"");
+        assert_eq!(parse_err("k!v"), "invalid operator in metadata predicate \"k!v\"");
+        assert_eq!(parse_err("k^v"), "invalid operator in metadata predicate \"k^v\"");
+        assert_eq!(
+            parse_err("k<abc"),
+            "\"abc\" is not a number in metadata predicate \"k<abc\""
+        );
+        assert_eq!(parse_err("k>"), "\"\" is not a number in metadata predicate \"k>\"");
+        assert_eq!(parse_err("k<NaN"), "\"NaN\" is not a number in metadata predicate \"k<NaN\"");
+        assert_eq!(parse_err("k>=inf"), "\"inf\" is not a number in metadata predicate \"k>=inf\"");
+        // A later malformed clause fails the whole filter.
+        assert!("a=1,b<x".parse::<MetadataFilter>().is_err());
+    }
+
+    #[test]
+    fn evaluates_each_operator() {
+        let metadata = metadata(&[("type", "image/png"), ("size", " 1024 "), ("name", "x")]);
+        let matches = |s: &str| s.parse::<MetadataFilter>().unwrap().matches(&metadata);
+
+        assert!(matches("type") && !matches("other"));
+        assert!(matches("!other") && !matches("!type"));
+        assert!(matches("type=image/png") && !matches("type=image/"));
+        assert!(matches("type!=image/") && !matches("type!=image/png"));
+        assert!(matches("other!=x"), "absent keys aren't equal to anything");
+        assert!(matches("type^=image/") && matches("type^=") && !matches("type^=text/"));
+        assert!(!matches("other^="));
+        assert!(matches("size<1025") && !matches("size<1024"));
+        assert!(matches("size<=1024") && !matches("size<=1023"));
+        assert!(matches("size>1023") && !matches("size>1024"));
+        assert!(matches("size>=1024") && !matches("size>=1025"));
+        // Values that aren't numbers, and absent keys, never compare.
+        assert!(!matches("name<1") && !matches("name>=1"));
+        assert!(!matches("other<1") && !matches("other>=1"));
+    }
+
+    #[test]
+    fn evaluates_conjunctions() {
+        let metadata = metadata(&[("a", "1"), ("b", "2")]);
+        let matches = |s: &str| s.parse::<MetadataFilter>().unwrap().matches(&metadata);
+        assert!(matches(""));
+        assert!(matches("a=1,b=2"));
+        assert!(!matches("a=1,b=3"));
+        assert!(MetadataFilter::new().and(Predicate::Exists("a".to_string())).matches(&metadata));
+        assert!(MetadataFilter::new().matches(&[]));
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in [
+            "k",
+            "!k",
+            "k=v",
+            "k=",
+            "k!=\"a,b\"",
+            "k^=\" padded\"",
+            "k<1.5",
+            "k<=-2",
+            "k>1000",
+            "k>=0,j=x,!l",
+        ] {
+            let filter: MetadataFilter = s.parse().unwrap();
+            assert_eq!(filter.to_string(), s);
+            assert_eq!(filter.to_string().parse::<MetadataFilter>().unwrap(), filter);
+        }
+    }
+
+    #[test]
+    fn lists_matching_objects() {
+        let mut model = BucketModel::new(100);
+        for (key, kind) in [("a", "image"), ("b", "text"), ("c/d", "image"), ("e", "image")] {
+            model
+                .add_object(&addObject_1Call {
+                    source: B256::ZERO,
+                    key: key.to_string(),
+                    hash: B256::repeat_byte(1),
+                    recoveryHash: B256::ZERO,
+                    size: 1,
+                    ttl: 0,
+                    metadata: metadata(&[("type", kind)]),
+                    overwrite: false,
+                })
+                .unwrap();
+        }
+        let keys = list_objects(&mut model, Address::ZERO)
+            .delimiter("/")
+            .page_size(1)
+            .matching("type=image".parse().unwrap())
+            .map(|object| object.map(|object| object.key))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+        // The common prefix `c/` is skipped.
+        assert_eq!(keys, ["a", "e"]);
+    }
+}
@@ -0,0 +1,509 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use alloy_primitives::{Address, B256};
+use anyhow::{anyhow, bail, Context};
+
+use super::{addObject_1Call, deleteObjectCall, KeyValue, Object};
+use crate::executor::CallExecutor;
+
+/// A file of a local directory manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalFile {
+    /// The path relative to the synced directory, with `/` separators.
+    pub path: String,
+    pub size: u64,
+    /// The blake3 hash of the file contents.
+    pub hash: B256,
+    /// The recovery hash of the uploaded blob, or zero if not known yet.
+    pub recovery_hash: B256,
+}
+
+/// Builds a manifest of the regular files under `root`, hashing their contents. Paths are
+/// sorted.
+///
+/// Symlinks are skipped rather than followed, so a link can't pull in files from outside `root`
+/// or loop back into it. FIFOs, sockets and device files are skipped too.
+pub fn scan_dir(root: impl AsRef<Path>) -> anyhow::Result<Vec<LocalFile>> {
+    let root = root.as_ref();
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let entries =
+            fs::read_dir(&dir).with_context(|| format!("failed to read {}", dir.display()))?;
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            // Unlike `Path::is_dir`, `DirEntry::file_type` doesn't follow symlinks.
+            let file_type = entry
+                .file_type()
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            if file_type.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+            let relative = path.strip_prefix(root).expect("path is under root");
+            let components = relative
+                .components()
+                .map(|c| {
+                    c.as_os_str()
+                        .to_str()
+                        .ok_or_else(|| anyhow!("path {} is not valid UTF-8", path.display()))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let mut hasher = blake3::Hasher::new();
+            fs::File::open(&path)
+                .and_then(|file| hasher.update_reader(file).map(|_| ()))
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            files.push(LocalFile {
+                path: components.join("/"),
+                size: hasher.count(),
+                hash: B256::from(*hasher.finalize().as_bytes()),
+                recovery_hash: B256::ZERO,
+            });
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// An operation of a [`SyncPlan`].
+#[derive(Clone)]
+pub enum SyncAction {
+    /// Adds an object whose key doesn't exist in the bucket.
+    Add(addObject_1Call),
+    /// Overwrites an object whose contents differ.
+    Replace(addObject_1Call),
+    /// Deletes an object that doesn't exist locally.
+    Delete(deleteObjectCall),
+}
+
+impl SyncAction {
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Add(call) | Self::Replace(call) => &call.key,
+            Self::Delete(call) => &call.key,
+        }
+    }
+}
+
+impl fmt::Display for SyncAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Add(call) => write!(f, "add     {} ({} bytes)", call.key, call.size),
+            Self::Replace(call) => write!(f, "replace {} ({} bytes)", call.key, call.size),
+            Self::Delete(call) => write!(f, "delete  {}", call.key),
+        }
+    }
+}
+
+/// The operations that make a bucket match a local directory.
+///
+/// Displays as a dry run: one line per action, followed by a summary.
+#[derive(Clone, Default)]
+pub struct SyncPlan {
+    pub actions: Vec<SyncAction>,
+    /// Keys of objects that already match.
+    pub unchanged: Vec<String>,
+}
+
+impl SyncPlan {
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Executes the actions against the bucket at `bucket` in order, stopping at the first
+    /// failure. Returns the number of actions executed.
+    pub fn execute<E: CallExecutor>(
+        &self,
+        executor: &mut E,
+        bucket: Address,
+    ) -> anyhow::Result<usize> {
+        for (i, action) in self.actions.iter().enumerate() {
+            let result = match action {
+                SyncAction::Add(call) | SyncAction::Replace(call) => {
+                    executor.call(bucket, call).map(|_| ())
+                }
+                SyncAction::Delete(call) => executor.call(bucket, call).map(|_| ()),
+            };
+            result.with_context(|| {
+                format!("action {} of {} failed: {}", i + 1, self.actions.len(), action)
+            })?;
+        }
+        Ok(self.actions.len())
+    }
+}
+
+impl fmt::Display for SyncPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (mut added, mut replaced, mut deleted) = (0, 0, 0);
+        for action in &self.actions {
+            writeln!(f, "{}", action)?;
+            match action {
+                SyncAction::Add(_) => added += 1,
+                SyncAction::Replace(_) => replaced += 1,
+                SyncAction::Delete(_) => deleted += 1,
+            }
+        }
+        write!(
+            f,
+            "{} to add, {} to replace, {} to delete, {} unchanged",
+            added,
+            replaced,
+            deleted,
+            self.unchanged.len()
+        )
+    }
+}
+
+/// Plans the operations that make the objects under a bucket prefix match a local directory.
+///
+/// A local file at `path` maps to the key `prefix + path`. Objects are compared by blob hash and
+/// size; metadata is not compared.
+#[derive(Clone)]
+pub struct SyncPlanner {
+    source: B256,
+    prefix: String,
+    ttl: u64,
+    metadata: Vec<KeyValue>,
+    delete: bool,
+}
+
+impl SyncPlanner {
+    /// Returns a planner for blobs uploaded to the node with ID `source`.
+    pub fn new(source: B256) -> Self {
+        Self {
+            source,
+            prefix: String::new(),
+            ttl: 0,
+            metadata: Vec::new(),
+            delete: false,
+        }
+    }
+
+    /// Sets the bucket key prefix the local directory maps to, e.g. `backups/`.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Sets the `ttl` of added objects. Zero uses the bucket default.
+    pub fn ttl(mut self, ttl: u64) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the metadata of added objects.
+    pub fn metadata(mut self, metadata: Vec<KeyValue>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Deletes objects under the prefix that don't exist locally. Off by default.
+    pub fn delete(mut self, delete: bool) -> Self {
+        self.delete = delete;
+        self
+    }
+
+    /// Returns the bucket key of a local path.
+    pub fn key_for(&self, path: &str) -> anyhow::Result<String> {
+        // Also rejects empty, absolute and directory paths, which have empty components.
+        if path.split('/').any(|c| c.is_empty() || c == "." || c == "..") {
+            bail!("invalid local path {:?}", path);
+        }
+        Ok(format!("{}{}", self.prefix, path))
+    }
+
+    /// Plans a sync from the `local` manifest to a `remote` listing, e.g. collected with
+    /// [`list_objects`](super::list_objects) and the same prefix. Remote objects outside the
+    /// prefix are ignored.
+    pub fn plan(&self, local: &[LocalFile], remote: &[Object]) -> anyhow::Result<SyncPlan> {
+        let mut files = BTreeMap::new();
+        for file in local {
+            let key = self.key_for(&file.path)?;
+            if files.insert(key.clone(), file).is_some() {
+                bail!("duplicate local path {:?}", file.path);
+            }
+        }
+        let remote: HashMap<&str, &Object> = remote
+            .iter()
+            .filter(|o| o.key.starts_with(&self.prefix))
+            .map(|o| (o.key.as_str(), o))
+            .collect();
+
+        let mut plan = SyncPlan::default();
+        for (key, file) in &files {
+            match remote.get(key.as_str()) {
+                Some(o) if o.state.blobHash == file.hash && o.state.size == file.size => {
+                    plan.unchanged.push(key.clone());
+                }
+                Some(_) => plan.actions.push(SyncAction::Replace(self.add_call(key, file, true))),
+                None => plan.actions.push(SyncAction::Add(self.add_call(key, file, false))),
+            }
+        }
+        if self.delete {
+            let mut deleted: Vec<&str> = remote
+                .keys()
+                .copied()
+                .filter(|key| !files.contains_key(*key))
+                .collect();
+            deleted.sort();
+            plan.actions.extend(deleted.into_iter().map(|key| {
+                SyncAction::Delete(deleteObjectCall {
+                    key: key.to_string(),
+                })
+            }));
+        }
+        Ok(plan)
+    }
+
+    fn add_call(&self, key: &str, file: &LocalFile, overwrite: bool) -> addObject_1Call {
+        addObject_1Call {
+            source: self.source,
+            key: key.to_string(),
+            hash: file.hash,
+            recoveryHash: file.recovery_hash,
+            size: file.size,
+            ttl: self.ttl,
+            metadata: self.metadata.clone(),
+            overwrite,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::bucket::{list_objects, BucketModel, ListEntry, ObjectState};
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn scans_regular_files() {
+        let dir = TempDir::new("recall-scan-files");
+        fs::create_dir_all(dir.0.join("a/b")).unwrap();
+        fs::create_dir_all(dir.0.join("empty")).unwrap();
+        fs::write(dir.0.join("top.txt"), b"top").unwrap();
+        fs::write(dir.0.join("a/b/deep.bin"), b"").unwrap();
+
+        let files = scan_dir(&dir.0).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["a/b/deep.bin", "top.txt"]);
+        assert_eq!(files[1].size, 3);
+        assert_eq!(files[1].hash, B256::from(*blake3::hash(b"top").as_bytes()));
+        assert_eq!(files[0].hash, B256::from(*blake3::hash(b"").as_bytes()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn skips_symlinks_and_special_files() {
+        use std::os::unix::fs::symlink;
+
+        let dir = TempDir::new("recall-scan-special");
+        let outside = TempDir::new("recall-scan-outside");
+        fs::write(outside.0.join("secret"), b"secret").unwrap();
+        fs::create_dir_all(dir.0.join("sub")).unwrap();
+        fs::write(dir.0.join("sub/file"), b"file").unwrap();
+        // A cycle back to the root, a link out of it and a link to a file.
+        symlink(&dir.0, dir.0.join("sub/loop")).unwrap();
+        symlink(&outside.0, dir.0.join("outside")).unwrap();
+        symlink(dir.0.join("sub/file"), dir.0.join("link")).unwrap();
+        let fifo = std::process::Command::new("mkfifo")
+            .arg(dir.0.join("fifo"))
+            .status()
+            .is_ok_and(|s| s.success());
+
+        let files = scan_dir(&dir.0).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["sub/file"]);
+        assert!(!fifo || dir.0.join("fifo").exists());
+    }
+
+    fn file(path: &str, contents: u8, size: u64) -> LocalFile {
+        LocalFile {
+            path: path.to_string(),
+            size,
+            hash: B256::repeat_byte(contents),
+            recovery_hash: B256::repeat_byte(0xee),
+        }
+    }
+
+    fn object(key: &str, contents: u8, size: u64) -> Object {
+        Object {
+            key: key.to_string(),
+            state: ObjectState {
+                blobHash: B256::repeat_byte(contents),
+                size,
+                expiry: 100,
+                metadata: Vec::new(),
+            },
+        }
+    }
+
+    fn summary(plan: &SyncPlan) -> Vec<String> {
+        plan.actions.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn plans_add_replace_and_unchanged() {
+        let local = [
+            file("new", 1, 1),
+            file("same", 2, 2),
+            file("edited", 3, 3),
+            file("grown", 4, 5),
+        ];
+        let remote = [object("same", 2, 2), object("edited", 9, 3), object("grown", 4, 4)];
+        let plan = SyncPlanner::new(B256::ZERO).plan(&local, &remote).unwrap();
+        assert_eq!(
+            summary(&plan),
+            ["replace edited (3 bytes)", "replace grown (5 bytes)", "add     new (1 bytes)"]
+        );
+        assert_eq!(plan.unchanged, ["same"]);
+        for action in &plan.actions {
+            let (SyncAction::Add(call) | SyncAction::Replace(call)) = action else {
+                panic!("unexpected {}", action);
+            };
+            assert_eq!(call.overwrite, matches!(action, SyncAction::Replace(_)));
+        }
+    }
+
+    #[test]
+    fn builds_add_calls() {
+        let metadata = vec![KeyValue {
+            key: "owner".to_string(),
+            value: "alice".to_string(),
+        }];
+        let planner = SyncPlanner::new(B256::repeat_byte(0x50))
+            .prefix("backup/")
+            .ttl(3600)
+            .metadata(metadata);
+        let plan = planner.plan(&[file("a/b.txt", 1, 7)], &[]).unwrap();
+        let SyncAction::Add(call) = &plan.actions[0] else {
+            panic!("expected an add");
+        };
+        assert_eq!(call.key, "backup/a/b.txt");
+        assert_eq!((call.source, call.hash), (B256::repeat_byte(0x50), B256::repeat_byte(1)));
+        assert_eq!(call.recoveryHash, B256::repeat_byte(0xee));
+        assert_eq!((call.size, call.ttl, call.overwrite), (7, 3600, false));
+        assert_eq!(call.metadata[0].value, "alice");
+    }
+
+    #[test]
+    fn maps_paths_under_prefix() {
+        let local = [file("a", 1, 1), file("b", 2, 2)];
+        let remote = [object("backup/a", 1, 1), object("a", 9, 9), object("backup2/b", 2, 2)];
+        let planner = SyncPlanner::new(B256::ZERO).prefix("backup/");
+        let plan = planner.plan(&local, &remote).unwrap();
+        // `a` and `backup2/b` are outside the prefix, so they don't match.
+        assert_eq!(summary(&plan), ["add     backup/b (2 bytes)"]);
+        assert_eq!(plan.unchanged, ["backup/a"]);
+    }
+
+    #[test]
+    fn deletes_only_under_prefix_in_order() {
+        let local = [file("keep", 1, 1)];
+        let remote = [
+            object("p/zzz", 1, 1),
+            object("p/keep", 1, 1),
+            object("p/aaa", 1, 1),
+            object("other", 1, 1),
+            object("p/sub/m", 1, 1),
+        ];
+        let planner = SyncPlanner::new(B256::ZERO).prefix("p/");
+        let plan = planner.clone().plan(&local, &remote).unwrap();
+        assert!(plan.is_empty());
+        let plan = planner.delete(true).plan(&local, &remote).unwrap();
+        assert_eq!(summary(&plan), ["delete  p/aaa", "delete  p/sub/m", "delete  p/zzz"]);
+        assert_eq!(plan.unchanged, ["p/keep"]);
+    }
+
+    #[test]
+    fn rejects_duplicate_and_invalid_paths() {
+        let planner = SyncPlanner::new(B256::ZERO);
+        let err = planner.plan(&[file("a", 1, 1), file("a", 2, 2)], &[]).err().unwrap();
+        assert_eq!(err.to_string(), "duplicate local path \"a\"");
+
+        for path in ["", "/abs", "a//b", "a/", ".", "..", "a/./b", "a/../b"] {
+            let err = planner.key_for(path).unwrap_err();
+            assert_eq!(err.to_string(), format!("invalid local path {:?}", path));
+            assert!(planner.plan(&[file(path, 1, 1)], &[]).is_err());
+        }
+        assert_eq!(planner.prefix("p/").key_for("a/.b/c..").unwrap(), "p/a/.b/c..");
+    }
+
+    #[test]
+    fn displays_dry_run() {
+        let local = [file("a", 1, 1), file("b", 2, 2), file("c", 3, 3)];
+        let remote = [object("b", 2, 2), object("c", 4, 3), object("d", 4, 4)];
+        let plan = SyncPlanner::new(B256::ZERO).delete(true).plan(&local, &remote).unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "add     a (1 bytes)\n\
+             replace c (3 bytes)\n\
+             delete  d\n\
+             1 to add, 1 to replace, 1 to delete, 1 unchanged"
+        );
+        assert_eq!(
+            SyncPlan::default().to_string(),
+            "0 to add, 0 to replace, 0 to delete, 0 unchanged"
+        );
+    }
+
+    #[test]
+    fn executes_plan_against_bucket() {
+        let mut model = BucketModel::new(100);
+        let planner = SyncPlanner::new(B256::ZERO).prefix("p/").delete(true);
+        let list = |model: &mut BucketModel| {
+            list_objects(model, Address::ZERO)
+                .map(|entry| match entry.unwrap() {
+                    ListEntry::Object(object) => (object.key, object.state.size),
+                    ListEntry::CommonPrefix(prefix) => panic!("unexpected {}", prefix),
+                })
+                .collect::<Vec<_>>()
+        };
+        let first = [file("a", 1, 1), file("b", 2, 2)];
+        let plan = planner.plan(&first, &[]).unwrap();
+        assert_eq!(plan.execute(&mut model, Address::ZERO).unwrap(), 2);
+
+        let second = [file("a", 1, 1), file("c", 3, 3)];
+        let remote: Vec<_> = list_objects(&mut model, Address::ZERO)
+            .filter_map(|entry| match entry.unwrap() {
+                ListEntry::Object(object) => Some(object),
+                ListEntry::CommonPrefix(_) => None,
+            })
+            .collect();
+        let plan = planner.plan(&second, &remote).unwrap();
+        assert_eq!(plan.execute(&mut model, Address::ZERO).unwrap(), 2);
+        assert_eq!(list(&mut model), [("p/a".to_string(), 1), ("p/c".to_string(), 3)]);
+
+        // A failed action stops execution and names the action.
+        let plan = planner.plan(&[file("a", 9, 1), file("c", 3, 3)], &[]).unwrap();
+        let err = plan.execute(&mut model, Address::ZERO).err().unwrap();
+        assert!(err.to_string().starts_with("action 1 of 2 failed: add     p/a"), "{}", err);
+    }
+}
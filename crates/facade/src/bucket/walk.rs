@@ -0,0 +1,426 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use alloy_primitives::Address;
+
+use super::{list_objects, ListEntry, Object, DEFAULT_DELIMITER};
+use crate::executor::CallExecutor;
+
+/// The order in which a [`TreeWalker`] visits directories.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WalkOrder {
+    #[default]
+    DepthFirst,
+    BreadthFirst,
+}
+
+/// A prefix and what is directly under it.
+#[derive(Clone)]
+pub struct Directory {
+    pub prefix: String,
+    /// Zero for the root of the walk.
+    pub depth: usize,
+    pub objects: Vec<Object>,
+    /// Common prefixes directly under `prefix`, i.e. subdirectories.
+    pub prefixes: Vec<String>,
+}
+
+/// A directory and its descendants, as far as the walk's maximum depth.
+#[derive(Clone)]
+pub struct TreeNode {
+    pub prefix: String,
+    pub depth: usize,
+    pub objects: Vec<Object>,
+    pub children: Vec<TreeNode>,
+    /// Subdirectories beyond the maximum depth, which were not listed.
+    pub unexpanded: Vec<String>,
+}
+
+impl TreeNode {
+    /// Total size of the objects in this node and its listed descendants.
+    ///
+    /// Widened to `u128`, since the sizes of many objects can overflow `u64`.
+    pub fn size(&self) -> u128 {
+        total_size(&self.objects) + self.children.iter().map(TreeNode::size).sum::<u128>()
+    }
+
+    /// Number of objects in this node and its listed descendants.
+    pub fn num_objects(&self) -> usize {
+        self.objects.len() + self.children.iter().map(TreeNode::num_objects).sum::<usize>()
+    }
+}
+
+/// Storage used under a prefix, including all descendants.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub prefix: String,
+    pub depth: usize,
+    pub objects: u64,
+    /// Sum of `ObjectState.size`, widened to `u128` like [`TreeNode::size`].
+    pub bytes: u128,
+}
+
+impl fmt::Display for DiskUsage {
+    /// Formats like a `du` line: the size, a tab and the prefix.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\t{}", self.bytes, self.prefix)
+    }
+}
+
+/// Walks a bucket's keys like a directory tree, descending into `Query.commonPrefixes`.
+#[derive(Clone, Debug)]
+pub struct TreeWalker {
+    bucket: Address,
+    delimiter: String,
+    order: WalkOrder,
+    max_depth: Option<usize>,
+    page_size: u64,
+}
+
+impl TreeWalker {
+    pub fn new(bucket: Address) -> Self {
+        Self {
+            bucket,
+            delimiter: DEFAULT_DELIMITER.to_string(),
+            order: WalkOrder::default(),
+            max_depth: None,
+            page_size: 1000,
+        }
+    }
+
+    /// Sets the directory separator. Defaults to `/`.
+    pub fn delimiter(mut self, delimiter: impl Into<String>) -> Self {
+        self.delimiter = delimiter.into();
+        self
+    }
+
+    pub fn order(mut self, order: WalkOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Stops descending below `depth`, where the root of the walk has depth zero.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Sets the number of objects fetched per `queryObjects` call.
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Visits each directory under `root` in the configured order, up to the maximum depth.
+    pub fn walk<E, F>(&self, executor: &mut E, root: &str, mut visit: F) -> anyhow::Result<()>
+    where
+        E: CallExecutor,
+        F: FnMut(&Directory) -> anyhow::Result<()>,
+    {
+        let mut queue = VecDeque::from([(root.to_string(), 0)]);
+        while let Some((prefix, depth)) = match self.order {
+            WalkOrder::BreadthFirst => queue.pop_front(),
+            WalkOrder::DepthFirst => queue.pop_back(),
+        } {
+            let dir = self.list(executor, prefix, depth)?;
+            visit(&dir)?;
+            if self.descends(depth) {
+                let children = dir.prefixes.iter().map(|p| (p.clone(), depth + 1));
+                match self.order {
+                    WalkOrder::BreadthFirst => queue.extend(children),
+                    // Push in reverse, so children are popped in listing order.
+                    WalkOrder::DepthFirst => queue.extend(children.rev()),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the tree under `root`, up to the maximum depth.
+    pub fn tree<E: CallExecutor>(&self, executor: &mut E, root: &str) -> anyhow::Result<TreeNode> {
+        self.build(executor, root.to_string(), 0)
+    }
+
+    /// Returns the storage used under `root` and each directory up to the maximum depth, with
+    /// children before their parents, like `du --max-depth`.
+    ///
+    /// Directories beyond the maximum depth are still listed, so the totals are complete.
+    pub fn du<E: CallExecutor>(
+        &self,
+        executor: &mut E,
+        root: &str,
+    ) -> anyhow::Result<Vec<DiskUsage>> {
+        let mut rows = Vec::new();
+        self.du_rec(executor, root.to_string(), 0, &mut rows)?;
+        Ok(rows)
+    }
+
+    fn descends(&self, depth: usize) -> bool {
+        self.max_depth.is_none_or(|max| depth < max)
+    }
+
+    fn list<E: CallExecutor>(
+        &self,
+        executor: &mut E,
+        prefix: String,
+        depth: usize,
+    ) -> anyhow::Result<Directory> {
+        let mut dir = Directory {
+            prefix,
+            depth,
+            objects: Vec::new(),
+            prefixes: Vec::new(),
+        };
+        let lister = list_objects(executor, self.bucket)
+            .prefix(dir.prefix.clone())
+            .delimiter(self.delimiter.clone())
+            .page_size(self.page_size);
+        for entry in lister {
+            match entry? {
+                ListEntry::Object(object) => dir.objects.push(object),
+                ListEntry::CommonPrefix(prefix) => dir.prefixes.push(prefix),
+            }
+        }
+        dir.prefixes.sort();
+        Ok(dir)
+    }
+
+    fn build<E: CallExecutor>(
+        &self,
+        executor: &mut E,
+        prefix: String,
+        depth: usize,
+    ) -> anyhow::Result<TreeNode> {
+        let dir = self.list(executor, prefix, depth)?;
+        let mut node = TreeNode {
+            prefix: dir.prefix,
+            depth,
+            objects: dir.objects,
+            children: Vec::new(),
+            unexpanded: Vec::new(),
+        };
+        if self.descends(depth) {
+            for prefix in dir.prefixes {
+                node.children.push(self.build(executor, prefix, depth + 1)?);
+            }
+        } else {
+            node.unexpanded = dir.prefixes;
+        }
+        Ok(node)
+    }
+
+    fn du_rec<E: CallExecutor>(
+        &self,
+        executor: &mut E,
+        prefix: String,
+        depth: usize,
+        rows: &mut Vec<DiskUsage>,
+    ) -> anyhow::Result<(u64, u128)> {
+        let dir = self.list(executor, prefix, depth)?;
+        let mut objects = dir.objects.len() as u64;
+        let mut bytes = total_size(&dir.objects);
+        for prefix in dir.prefixes {
+            let (o, b) = self.du_rec(executor, prefix, depth + 1, rows)?;
+            objects += o;
+            bytes += b;
+        }
+        if self.max_depth.is_none_or(|max| depth <= max) {
+            rows.push(DiskUsage {
+                prefix: dir.prefix,
+                depth,
+                objects,
+                bytes,
+            });
+        }
+        Ok((objects, bytes))
+    }
+}
+
+fn total_size(objects: &[Object]) -> u128 {
+    objects.iter().map(|o| u128::from(o.state.size)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::B256;
+    use anyhow::bail;
+
+    use super::*;
+    use crate::bucket::{addObject_1Call, BucketModel};
+
+    fn model(objects: &[(&str, u64)]) -> BucketModel {
+        let mut model = BucketModel::new(100);
+        for (key, size) in objects {
+            model
+                .add_object(&addObject_1Call {
+                    source: B256::ZERO,
+                    key: key.to_string(),
+                    hash: B256::repeat_byte(1),
+                    recoveryHash: B256::ZERO,
+                    size: *size,
+                    ttl: 0,
+                    metadata: Vec::new(),
+                    overwrite: false,
+                })
+                .unwrap();
+        }
+        model
+    }
+
+    fn bucket() -> BucketModel {
+        model(&[
+            ("a.txt", 1),
+            ("docs/x", 2),
+            ("docs/y", 3),
+            ("docs/sub/z", 4),
+            ("img/p", 5),
+            ("img/deep/er/q", 6),
+        ])
+    }
+
+    fn visited(walker: &TreeWalker, root: &str) -> Vec<(String, usize)> {
+        let mut dirs = Vec::new();
+        walker
+            .walk(&mut bucket(), root, |dir| {
+                dirs.push((dir.prefix.clone(), dir.depth));
+                Ok(())
+            })
+            .unwrap();
+        dirs
+    }
+
+    fn prefixes(dirs: Vec<(String, usize)>) -> Vec<String> {
+        dirs.into_iter().map(|(prefix, _)| prefix).collect()
+    }
+
+    fn keys(objects: &[Object]) -> Vec<&str> {
+        objects.iter().map(|o| o.key.as_str()).collect()
+    }
+
+    #[test]
+    fn walks_depth_first() {
+        let walker = TreeWalker::new(Address::ZERO).page_size(1);
+        assert_eq!(
+            visited(&walker, ""),
+            [
+                ("".to_string(), 0),
+                ("docs/".to_string(), 1),
+                ("docs/sub/".to_string(), 2),
+                ("img/".to_string(), 1),
+                ("img/deep/".to_string(), 2),
+                ("img/deep/er/".to_string(), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn walks_breadth_first() {
+        let walker = TreeWalker::new(Address::ZERO).order(WalkOrder::BreadthFirst);
+        assert_eq!(
+            prefixes(visited(&walker, "")),
+            ["", "docs/", "img/", "docs/sub/", "img/deep/", "img/deep/er/"]
+        );
+    }
+
+    #[test]
+    fn stops_at_max_depth() {
+        let walker = TreeWalker::new(Address::ZERO).max_depth(1);
+        assert_eq!(prefixes(visited(&walker, "")), ["", "docs/", "img/"]);
+        let walker = walker.max_depth(0);
+        assert_eq!(prefixes(visited(&walker, "")), [""]);
+        // Depths are relative to the root of the walk.
+        let walker = walker.max_depth(1).order(WalkOrder::BreadthFirst);
+        assert_eq!(
+            visited(&walker, "img/"),
+            [("img/".to_string(), 0), ("img/deep/".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn visit_errors_stop_the_walk() {
+        let mut count = 0;
+        let err = TreeWalker::new(Address::ZERO)
+            .walk(&mut bucket(), "", |dir| {
+                count += 1;
+                if dir.prefix == "docs/" {
+                    bail!("stop");
+                }
+                Ok(())
+            })
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "stop");
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn builds_tree() {
+        let tree = TreeWalker::new(Address::ZERO).tree(&mut bucket(), "").unwrap();
+        assert_eq!((tree.prefix.as_str(), tree.depth), ("", 0));
+        assert_eq!(keys(&tree.objects), ["a.txt"]);
+        let children: Vec<_> = tree.children.iter().map(|c| c.prefix.as_str()).collect();
+        assert_eq!(children, ["docs/", "img/"]);
+        let docs = &tree.children[0];
+        assert_eq!(keys(&docs.objects), ["docs/x", "docs/y"]);
+        assert_eq!(docs.children[0].prefix, "docs/sub/");
+        assert_eq!(docs.children[0].depth, 2);
+        assert!(tree.unexpanded.is_empty());
+        assert_eq!((tree.size(), tree.num_objects()), (21, 6));
+        assert_eq!((docs.size(), docs.num_objects()), (9, 3));
+    }
+
+    #[test]
+    fn tree_leaves_deep_directories_unexpanded() {
+        let walker = TreeWalker::new(Address::ZERO).max_depth(1);
+        let tree = walker.tree(&mut bucket(), "").unwrap();
+        let docs = &tree.children[0];
+        assert!(docs.children.is_empty());
+        assert_eq!(docs.unexpanded, ["docs/sub/"]);
+        assert_eq!(tree.children[1].unexpanded, ["img/deep/"]);
+        // Only listed objects count.
+        assert_eq!((tree.size(), tree.num_objects()), (11, 4));
+    }
+
+    #[test]
+    fn du_totals() {
+        let rows = TreeWalker::new(Address::ZERO).du(&mut bucket(), "").unwrap();
+        let rows: Vec<_> = rows
+            .iter()
+            .map(|r| (r.prefix.as_str(), r.depth, r.objects, r.bytes))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("docs/sub/", 2, 1, 4),
+                ("docs/", 1, 3, 9),
+                ("img/deep/er/", 3, 1, 6),
+                ("img/deep/", 2, 1, 6),
+                ("img/", 1, 2, 11),
+                ("", 0, 6, 21),
+            ]
+        );
+    }
+
+    #[test]
+    fn du_counts_beyond_max_depth() {
+        let walker = TreeWalker::new(Address::ZERO).max_depth(1);
+        let rows = walker.du(&mut bucket(), "").unwrap();
+        let lines: Vec<_> = rows.iter().map(|r| r.to_string()).collect();
+        assert_eq!(lines, ["9\tdocs/", "11\timg/", "21\t"]);
+        assert_eq!(rows[2].objects, 6);
+    }
+
+    #[test]
+    fn sizes_do_not_overflow() {
+        let mut model = model(&[("a", u64::MAX), ("b/c", u64::MAX), ("b/d", u64::MAX)]);
+        let walker = TreeWalker::new(Address::ZERO);
+        let total = 3 * u128::from(u64::MAX);
+        assert_eq!(walker.tree(&mut model, "").unwrap().size(), total);
+        let rows = walker.du(&mut model, "").unwrap();
+        assert_eq!(rows.last().unwrap().bytes, total);
+        assert_eq!(rows[0].bytes, 2 * u128::from(u64::MAX));
+    }
+}
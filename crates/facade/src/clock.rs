@@ -0,0 +1,261 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::fmt;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Converts between chain epochs (block heights) and wall-clock time.
+///
+/// Expiries such as `Subscription.expiry`, `ObjectValue.expiry`, `ObjectState.expiry` and
+/// `CreditApproval.expiry` are epochs. Implement this trait on top of block headers for exact
+/// times, or use [`LinearClock`] to extrapolate from a known block and the block time.
+pub trait ChainClock {
+    /// Returns the time of `epoch`.
+    fn epoch_to_time(&self, epoch: u64) -> anyhow::Result<DateTime<Utc>>;
+
+    /// Returns the last epoch at or before `time`.
+    fn time_to_epoch(&self, time: DateTime<Utc>) -> anyhow::Result<u64>;
+
+    /// Returns when `expiry` is reached, relative to `now`.
+    fn expiry(&self, expiry: u64, now: DateTime<Utc>) -> anyhow::Result<Expiry> {
+        let at = self.epoch_to_time(expiry)?;
+        Ok(Expiry {
+            epoch: expiry,
+            at,
+            remaining: at - now,
+        })
+    }
+
+    /// Returns the number of epochs from `now` until `until`, rounded up, e.g. to compute the
+    /// `ttl` for `addBlob`.
+    fn ttl_until(&self, now: DateTime<Utc>, until: DateTime<Utc>) -> anyhow::Result<u64> {
+        if until < now {
+            bail!("{} is before {}", until, now);
+        }
+        let start = self.time_to_epoch(now)?;
+        let mut end = self.time_to_epoch(until)?;
+        if self.epoch_to_time(end)? < until {
+            end += 1;
+        }
+        Ok(end - start)
+    }
+}
+
+/// A [`ChainClock`] that assumes a constant block time from an anchor block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinearClock {
+    anchor_epoch: u64,
+    anchor_time: DateTime<Utc>,
+    block_time: TimeDelta,
+}
+
+impl LinearClock {
+    /// Returns a clock anchored at `epoch` having been produced at `time`, e.g. the latest block.
+    pub fn new(epoch: u64, time: DateTime<Utc>, block_time: Duration) -> anyhow::Result<Self> {
+        let block_time = TimeDelta::from_std(block_time)
+            .ok()
+            .filter(|t| *t > TimeDelta::zero())
+            .ok_or_else(|| anyhow!("invalid block time {:?}", block_time))?;
+        Ok(Self {
+            anchor_epoch: epoch,
+            anchor_time: time,
+            block_time,
+        })
+    }
+
+    /// Returns a clock anchored at the genesis block.
+    pub fn from_genesis(genesis: DateTime<Utc>, block_time: Duration) -> anyhow::Result<Self> {
+        Self::new(0, genesis, block_time)
+    }
+
+    pub fn block_time(&self) -> Duration {
+        self.block_time.to_std().expect("block time is positive")
+    }
+
+    /// Returns the number of epochs covering at least `duration`, e.g. to compute a `ttl`.
+    pub fn ttl_for(&self, duration: Duration) -> u64 {
+        let block_nanos = self.block_time.num_nanoseconds().unwrap_or(i64::MAX) as u128;
+        duration.as_nanos().div_ceil(block_nanos).min(u128::from(u64::MAX)) as u64
+    }
+}
+
+impl ChainClock for LinearClock {
+    fn epoch_to_time(&self, epoch: u64) -> anyhow::Result<DateTime<Utc>> {
+        let blocks = i128::from(epoch) - i128::from(self.anchor_epoch);
+        let nanos = i128::from(self.block_time.num_nanoseconds().unwrap_or(i64::MAX));
+        i64::try_from(blocks * nanos)
+            .ok()
+            .and_then(|n| self.anchor_time.checked_add_signed(TimeDelta::nanoseconds(n)))
+            .ok_or_else(|| anyhow!("epoch {} is out of range", epoch))
+    }
+
+    fn time_to_epoch(&self, time: DateTime<Utc>) -> anyhow::Result<u64> {
+        let nanos = (time - self.anchor_time)
+            .num_nanoseconds()
+            .ok_or_else(|| anyhow!("{} is out of range", time))?;
+        let block_nanos = self.block_time.num_nanoseconds().unwrap_or(i64::MAX);
+        let epoch = i128::from(self.anchor_epoch) + i128::from(nanos.div_euclid(block_nanos));
+        u64::try_from(epoch).map_err(|_| anyhow!("{} is before epoch 0", time))
+    }
+}
+
+/// When an expiry epoch is reached. Displays as e.g. `expires in 3 days` or
+/// `expired 2 hours ago`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Expiry {
+    pub epoch: u64,
+    pub at: DateTime<Utc>,
+    /// Time left until `at`, negative if already expired.
+    pub remaining: TimeDelta,
+}
+
+impl Expiry {
+    pub fn is_expired(&self) -> bool {
+        self.remaining <= TimeDelta::zero()
+    }
+}
+
+impl fmt::Display for Expiry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_expired() {
+            write!(f, "expired {} ago", Approx(-self.remaining))
+        } else {
+            write!(f, "expires in {}", Approx(self.remaining))
+        }
+    }
+}
+
+/// Formats a duration in its largest whole unit, e.g. `3 days`.
+struct Approx(TimeDelta);
+
+impl fmt::Display for Approx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let units = [
+            (self.0.num_days(), "day"),
+            (self.0.num_hours(), "hour"),
+            (self.0.num_minutes(), "minute"),
+            (self.0.num_seconds(), "second"),
+        ];
+        let (n, unit) = units
+            .into_iter()
+            .find(|(n, _)| *n > 0)
+            .unwrap_or((0, "second"));
+        write!(f, "{} {}{}", n, unit, if n == 1 { "" } else { "s" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchor() -> DateTime<Utc> {
+        "2025-01-01T00:00:00Z".parse().unwrap()
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        anchor() + TimeDelta::seconds(secs)
+    }
+
+    /// Epoch 100 at the anchor time, with two-second blocks.
+    fn clock() -> LinearClock {
+        LinearClock::new(100, anchor(), Duration::from_secs(2)).unwrap()
+    }
+
+    #[test]
+    fn converts_epochs_to_times() {
+        let clock = clock();
+        assert_eq!(clock.epoch_to_time(100).unwrap(), anchor());
+        assert_eq!(clock.epoch_to_time(130).unwrap(), at(60));
+        assert_eq!(clock.epoch_to_time(70).unwrap(), at(-60));
+        assert_eq!(clock.epoch_to_time(0).unwrap(), at(-200));
+        assert_eq!(
+            clock.epoch_to_time(u64::MAX).unwrap_err().to_string(),
+            format!("epoch {} is out of range", u64::MAX)
+        );
+    }
+
+    #[test]
+    fn converts_times_to_epochs() {
+        let clock = clock();
+        assert_eq!(clock.time_to_epoch(anchor()).unwrap(), 100);
+        assert_eq!(clock.time_to_epoch(at(60)).unwrap(), 130);
+        // Times between blocks round down to the last epoch.
+        assert_eq!(clock.time_to_epoch(at(61)).unwrap(), 130);
+        assert_eq!(clock.time_to_epoch(at(59)).unwrap(), 129);
+        assert_eq!(clock.time_to_epoch(at(-1)).unwrap(), 99);
+        assert_eq!(clock.time_to_epoch(at(-200)).unwrap(), 0);
+        for epoch in [0, 1, 99, 100, 101, 1_000_000] {
+            let time = clock.epoch_to_time(epoch).unwrap();
+            assert_eq!(clock.time_to_epoch(time).unwrap(), epoch);
+        }
+    }
+
+    #[test]
+    fn rejects_times_before_genesis() {
+        let clock = LinearClock::from_genesis(anchor(), Duration::from_secs(2)).unwrap();
+        assert_eq!(clock.time_to_epoch(at(1)).unwrap(), 0);
+        assert_eq!(
+            clock.time_to_epoch(at(-1)).unwrap_err().to_string(),
+            "2024-12-31 23:59:59 UTC is before epoch 0"
+        );
+        assert!(clock.expiry(0, at(-1)).is_ok());
+    }
+
+    #[test]
+    fn rejects_zero_block_time() {
+        let err = LinearClock::new(0, anchor(), Duration::ZERO).unwrap_err();
+        assert_eq!(err.to_string(), "invalid block time 0ns");
+        let clock = LinearClock::new(0, anchor(), Duration::from_millis(1500)).unwrap();
+        assert_eq!(clock.block_time(), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn ttl_until_rounds_up() {
+        let clock = clock();
+        assert_eq!(clock.ttl_until(anchor(), at(60)).unwrap(), 30);
+        assert_eq!(clock.ttl_until(anchor(), at(61)).unwrap(), 31);
+        assert_eq!(clock.ttl_until(anchor(), anchor()).unwrap(), 0);
+        // Counted in epochs, so a start between blocks counts from its block.
+        assert_eq!(clock.ttl_until(at(1), at(4)).unwrap(), 2);
+        assert_eq!(clock.ttl_until(at(1), at(5)).unwrap(), 3);
+        assert!(clock.epoch_to_time(100 + 31).unwrap() >= at(61));
+        assert_eq!(
+            clock.ttl_until(at(1), anchor()).unwrap_err().to_string(),
+            "2025-01-01 00:00:00 UTC is before 2025-01-01 00:00:01 UTC"
+        );
+    }
+
+    #[test]
+    fn ttl_for_rounds_up() {
+        let clock = clock();
+        assert_eq!(clock.ttl_for(Duration::ZERO), 0);
+        assert_eq!(clock.ttl_for(Duration::from_secs(60)), 30);
+        assert_eq!(clock.ttl_for(Duration::from_secs(61)), 31);
+        assert_eq!(clock.ttl_for(Duration::from_nanos(1)), 1);
+        let fast = LinearClock::new(0, anchor(), Duration::from_nanos(1)).unwrap();
+        assert_eq!(fast.ttl_for(Duration::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn displays_expiry() {
+        let clock = clock();
+        let days = clock.expiry(100 + 3 * 86_400 / 2, anchor()).unwrap();
+        assert_eq!((days.at, days.remaining), (at(3 * 86_400), TimeDelta::days(3)));
+        assert!(!days.is_expired());
+        assert_eq!(days.to_string(), "expires in 3 days");
+        // Partial units are truncated.
+        let day = clock.expiry(100 + 86_400 / 2 + 1800, anchor()).unwrap();
+        assert_eq!(day.to_string(), "expires in 1 day");
+        assert_eq!(clock.expiry(101, anchor()).unwrap().to_string(), "expires in 2 seconds");
+
+        let hours = clock.expiry(100, at(2 * 3600 + 59)).unwrap();
+        assert!(hours.is_expired());
+        assert_eq!(hours.to_string(), "expired 2 hours ago");
+        let now = clock.expiry(100, anchor()).unwrap();
+        assert!(now.is_expired());
+        assert_eq!(now.to_string(), "expired 0 seconds ago");
+    }
+}
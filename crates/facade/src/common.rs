@@ -0,0 +1,100 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Types shared by several facades.
+//!
+//! The bindings are generated per facade, so a Solidity struct used by two facades becomes two
+//! unrelated Rust types. The types here are defined once and convert to and from each facade's
+//! copy with `From`, e.g. `bucket::KeyValue::from(kv)` or `common::KeyValue::from(kv)`.
+//!
+//! Only `KeyValue` is shared at the ABI level. The other types are re-exported from the one
+//! facade that has them: `Blob` and `Subscription` from the blobs facade, whose structs differ
+//! from those of `BlobTypes.sol`, and `Account` and `CreditApproval` from the credit facade.
+
+#[cfg(feature = "blobs")]
+pub use crate::blobs::{Blob, Subscription};
+#[cfg(feature = "credit")]
+pub use crate::credit::{Account, CreditApproval};
+
+alloy_sol_types::sol! {
+    /// The `KeyValue` struct of `CommonTypes.sol`, used for bucket and machine metadata.
+    #[derive(Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    struct KeyValue {
+        string key;
+        string value;
+    }
+}
+
+impl KeyValue {
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+macro_rules! facade_key_value_conversions {
+    ($(#[$attr:meta])* $from:path, $to:path) => {
+        $(#[$attr])*
+        impl From<$from> for $to {
+            fn from(kv: $from) -> Self {
+                Self {
+                    key: kv.key,
+                    value: kv.value,
+                }
+            }
+        }
+
+        $(#[$attr])*
+        impl From<&$from> for $to {
+            fn from(kv: &$from) -> Self {
+                Self {
+                    key: kv.key.clone(),
+                    value: kv.value.clone(),
+                }
+            }
+        }
+    };
+}
+
+facade_key_value_conversions!(#[cfg(feature = "bucket")] KeyValue, crate::bucket::KeyValue);
+facade_key_value_conversions!(#[cfg(feature = "bucket")] crate::bucket::KeyValue, KeyValue);
+facade_key_value_conversions!(#[cfg(feature = "machine")] KeyValue, crate::machine::KeyValue);
+facade_key_value_conversions!(#[cfg(feature = "machine")] crate::machine::KeyValue, KeyValue);
+facade_key_value_conversions!(
+    #[cfg(all(feature = "bucket", feature = "machine"))]
+    crate::bucket::KeyValue,
+    crate::machine::KeyValue
+);
+facade_key_value_conversions!(
+    #[cfg(all(feature = "bucket", feature = "machine"))]
+    crate::machine::KeyValue,
+    crate::bucket::KeyValue
+);
+
+#[cfg(test)]
+mod tests {
+    #[cfg(all(feature = "bucket", feature = "machine"))]
+    #[test]
+    fn converts_between_facades() {
+        use super::*;
+
+        let kv = KeyValue::new("alias", "photos");
+        let bucket = crate::bucket::KeyValue::from(&kv);
+        let machine = crate::machine::KeyValue::from(bucket);
+        assert_eq!(KeyValue::from(machine), kv);
+    }
+
+    #[cfg(all(feature = "blobs", feature = "credit"))]
+    #[test]
+    fn re_exports_facade_types() {
+        use super::*;
+
+        fn same<T>(_: Option<T>, _: Option<T>) {}
+        same(None::<Blob>, None::<crate::blobs::Blob>);
+        same(None::<Subscription>, None::<crate::blobs::Subscription>);
+        same(None::<Account>, None::<crate::credit::Account>);
+        same(None::<CreditApproval>, None::<crate::credit::CreditApproval>);
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use alloy_primitives::U256;
+use anyhow::anyhow;
+
+use super::ConfigSet;
+
+/// The subnet config as of the latest `ConfigSet` event, with typed fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubnetConfig {
+    /// Total storage capacity of the subnet, in bytes.
+    pub blob_capacity: u64,
+    /// Atto credits per whole token.
+    pub token_credit_rate: U256,
+    /// Epochs between blob credit debits.
+    pub blob_credit_debit_interval: u64,
+    /// Minimum blob TTL, in epochs.
+    pub blob_min_ttl: u64,
+    /// TTL applied when a blob is added without one, in epochs.
+    pub blob_default_ttl: u64,
+    pub blob_delete_batch_size: u64,
+    pub account_debit_batch_size: u64,
+}
+
+impl TryFrom<&ConfigSet> for SubnetConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &ConfigSet) -> Result<Self, Self::Error> {
+        Ok(Self {
+            blob_capacity: to_u64("blobCapacity", value.blobCapacity)?,
+            token_credit_rate: value.tokenCreditRate,
+            blob_credit_debit_interval: to_u64(
+                "blobCreditDebitInterval",
+                value.blobCreditDebitInterval,
+            )?,
+            blob_min_ttl: to_u64("blobMinTtl", value.blobMinTtl)?,
+            blob_default_ttl: to_u64("blobDefaultTtl", value.blobDefaultTtl)?,
+            blob_delete_batch_size: to_u64("blobDeleteBatchSize", value.blobDeleteBatchSize)?,
+            account_debit_batch_size: to_u64(
+                "accountDebitBatchSize",
+                value.accountDebitBatchSize,
+            )?,
+        })
+    }
+}
+
+impl TryFrom<ConfigSet> for SubnetConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ConfigSet) -> Result<Self, Self::Error> {
+        SubnetConfig::try_from(&value)
+    }
+}
+
+fn to_u64(field: &str, value: U256) -> anyhow::Result<u64> {
+    u64::try_from(value).map_err(|_| anyhow!("ConfigSet.{} overflows u64: {}", field, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_set() -> ConfigSet {
+        ConfigSet {
+            blobCapacity: U256::from(1u64 << 40),
+            tokenCreditRate: U256::MAX,
+            blobCreditDebitInterval: U256::from(600),
+            blobMinTtl: U256::from(3600),
+            blobDefaultTtl: U256::from(86_400),
+            blobDeleteBatchSize: U256::from(100),
+            accountDebitBatchSize: U256::from(u64::MAX),
+        }
+    }
+
+    #[test]
+    fn converts_config_set() {
+        let config = SubnetConfig::try_from(config_set()).unwrap();
+        assert_eq!(
+            config,
+            SubnetConfig {
+                blob_capacity: 1 << 40,
+                token_credit_rate: U256::MAX,
+                blob_credit_debit_interval: 600,
+                blob_min_ttl: 3600,
+                blob_default_ttl: 86_400,
+                blob_delete_batch_size: 100,
+                account_debit_batch_size: u64::MAX,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_fields_above_u64() {
+        let big = U256::from(u64::MAX) + U256::from(1);
+        let mut event = config_set();
+        event.blobMinTtl = big;
+        assert_eq!(
+            SubnetConfig::try_from(&event).unwrap_err().to_string(),
+            format!("ConfigSet.blobMinTtl overflows u64: {}", big)
+        );
+        let mut event = config_set();
+        event.blobCapacity = U256::MAX;
+        assert_eq!(
+            SubnetConfig::try_from(&event).unwrap_err().to_string(),
+            format!("ConfigSet.blobCapacity overflows u64: {}", U256::MAX)
+        );
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::fmt;
+
+use alloy_primitives::{U256, U512};
+
+use crate::amount::{atto_amount, format_decimal, RecallAmount};
+
+/// An amount of credit, stored in atto credits (10^-18 credit).
+///
+/// This is the unit of `Account.creditFree`, `CreditApproval.creditLimit`,
+/// `CreditDebited.amount` and friends.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Credit(U256);
+
+/// An amount of tokens, stored in atto (10^-18 RECALL).
+///
+/// This is the unit of `msg.value` in `buyCredit` and of `SubnetStats.balance`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Token(U256);
+
+atto_amount!(Credit, "credit");
+atto_amount!(Token, "RECALL");
+
+impl From<RecallAmount> for Token {
+    fn from(value: RecallAmount) -> Self {
+        Token(value.atto())
+    }
+}
+
+impl From<Token> for RecallAmount {
+    fn from(value: Token) -> Self {
+        RecallAmount::from_atto(value.0)
+    }
+}
+
+/// The number of credits one token buys, as reported by `ConfigSet.tokenCreditRate` and
+/// `SubnetStats.tokenCreditRate`.
+///
+/// The facades report the rate scaled by [`RATIO`](Self::RATIO), i.e. in atto credits per
+/// whole token. Conversions follow the blobs actor: both directions multiply first and then
+/// round down, so `credit_for(token_for(c)) <= c`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenCreditRate(U256);
+
+impl TokenCreditRate {
+    /// Scale of the reported rate.
+    pub const RATIO: U256 = RecallAmount::PRECISION;
+
+    /// Creates a rate from the scaled value reported by the facades.
+    pub const fn from_scaled(scaled: U256) -> Self {
+        TokenCreditRate(scaled)
+    }
+
+    /// Creates a rate from a whole number of credits per whole token.
+    pub fn from_credits_per_token(credits: u64) -> Self {
+        TokenCreditRate(U256::from(credits) * Self::RATIO)
+    }
+
+    /// The scaled rate, as reported by the facades.
+    pub const fn scaled(&self) -> U256 {
+        self.0
+    }
+
+    /// The credit bought with `token`, i.e. what `buyCredit` with `msg.value = token` yields.
+    ///
+    /// Returns `None` if the result overflows `U256`.
+    pub fn credit_for(&self, token: Token) -> Option<Credit> {
+        mul_div_floor(token.0, self.0, Self::RATIO).map(Credit)
+    }
+
+    /// The token value of `credit`, e.g. what a `CreditDebited.amount` costs.
+    ///
+    /// Returns `None` if the rate is zero.
+    pub fn token_for(&self, credit: Credit) -> Option<Token> {
+        mul_div_floor(credit.0, Self::RATIO, self.0).map(Token)
+    }
+
+    /// The smallest token amount for which [`credit_for`](Self::credit_for) yields at least
+    /// `credit`.
+    ///
+    /// Returns `None` if the rate is zero or the result overflows `U256`.
+    pub fn token_to_buy(&self, credit: Credit) -> Option<Token> {
+        if self.0.is_zero() {
+            return None;
+        }
+        let wide = credit.0.widening_mul::<256, 4, 512, 8>(Self::RATIO);
+        let rate = U512::from(self.0);
+        let (q, r) = wide.div_rem(rate);
+        let q = if r.is_zero() { q } else { q + U512::from(1) };
+        U256::checked_from_limbs_slice(q.as_limbs()).map(Token)
+    }
+}
+
+impl fmt::Display for TokenCreditRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let credits = format_decimal(self.0, RecallAmount::DECIMALS, f.precision());
+        write!(f, "{} credit/RECALL", credits)
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<&crate::config::ConfigSet> for TokenCreditRate {
+    fn from(value: &crate::config::ConfigSet) -> Self {
+        TokenCreditRate(value.tokenCreditRate)
+    }
+}
+
+#[cfg(feature = "blobs")]
+impl From<&crate::blobs::SubnetStats> for TokenCreditRate {
+    fn from(value: &crate::blobs::SubnetStats) -> Self {
+        TokenCreditRate(value.tokenCreditRate)
+    }
+}
+
+/// Computes `floor(a * b / c)` without intermediate overflow.
+fn mul_div_floor(a: U256, b: U256, c: U256) -> Option<U256> {
+    if c.is_zero() {
+        return None;
+    }
+    let wide = a.widening_mul::<256, 4, 512, 8>(b) / U512::from(c);
+    U256::checked_from_limbs_slice(wide.as_limbs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credit(atto: u64) -> Credit {
+        Credit::from_atto(U256::from(atto))
+    }
+
+    fn token(atto: u64) -> Token {
+        Token::from_atto(U256::from(atto))
+    }
+
+    #[test]
+    fn parses_and_formats_units() {
+        let credit: Credit = "2.5 credit".parse().unwrap();
+        assert_eq!(credit, "2.5".parse().unwrap());
+        assert_eq!(credit, "2500000000000000000 atto".parse().unwrap());
+        assert_eq!(credit.to_string(), "2.5 credit");
+        assert_eq!(format!("{:.2}", credit), "2.50 credit");
+        assert_eq!(".5 credit".parse::<Credit>().unwrap().to_string(), "0.5 credit");
+        assert_eq!("1.".parse::<Credit>().unwrap(), Credit::from_whole(1));
+
+        let token: Token = "1.5 recall".parse().unwrap();
+        assert_eq!(token.to_string(), "1.5 RECALL");
+        assert_eq!(RecallAmount::from(token), "1.5".parse().unwrap());
+        assert_eq!(Token::from(RecallAmount::from_whole(3)), Token::from_whole(3));
+    }
+
+    #[test]
+    fn rejects_invalid_units() {
+        let err = |s: &str| s.parse::<Credit>().unwrap_err().to_string();
+        assert_eq!(err("1 RECALL"), "invalid amount \"1 RECALL\": unknown unit \"RECALL\"");
+        assert_eq!(err("1e3"), "invalid amount \"1e3\": not a non-negative decimal number");
+        assert_eq!(
+            err("0.0000000000000000001"),
+            "invalid amount \"0.0000000000000000001\": more than 18 decimals"
+        );
+        let over = format!("{}0 atto", U256::MAX);
+        assert!(err(&over).ends_with("overflows 256 bits"));
+        assert!("1 credit".parse::<Token>().is_err());
+    }
+
+    #[test]
+    fn converts_at_rate() {
+        let rate = TokenCreditRate::from_credits_per_token(1000);
+        assert_eq!(rate.scaled(), U256::from(1000) * TokenCreditRate::RATIO);
+        assert_eq!(rate.to_string(), "1000 credit/RECALL");
+        assert_eq!(rate.credit_for(Token::from_whole(2)), Some(Credit::from_whole(2000)));
+        let half: Token = "0.5".parse().unwrap();
+        assert_eq!(rate.token_for(Credit::from_whole(500)), Some(half));
+        assert_eq!(rate.token_to_buy(Credit::from_whole(500)), Some(half));
+    }
+
+    #[test]
+    fn rounds_down_then_up_to_buy() {
+        // 1.5 atto credits per atto token, i.e. 1.5 credits per token.
+        let rate = TokenCreditRate::from_scaled(U256::from(1_500_000_000_000_000_000u64));
+        assert_eq!(rate.to_string(), "1.5 credit/RECALL");
+        assert_eq!(rate.credit_for(token(3)), Some(credit(4)));
+        // Exactly 4/3 atto tokens are needed, which floors to 1 and ceils to 2.
+        assert_eq!(rate.token_for(credit(2)), Some(token(1)));
+        assert_eq!(rate.token_to_buy(credit(2)), Some(token(2)));
+        assert_eq!(rate.credit_for(token(2)), Some(credit(3)));
+        assert_eq!(rate.credit_for(token(1)), Some(credit(1)));
+    }
+
+    #[test]
+    fn round_trip_never_gains() {
+        let rate = TokenCreditRate::from_scaled(U256::from(7_777_777_777_777u64));
+        for atto in [0u64, 1, 2, 999, 1_000_000_007, 123_456_789_012_345_678] {
+            let credit = credit(atto);
+            let token = rate.token_for(credit).unwrap();
+            assert!(rate.credit_for(token).unwrap() <= credit);
+            let to_buy = rate.token_to_buy(credit).unwrap();
+            assert!(to_buy >= token);
+            assert!(rate.credit_for(to_buy).unwrap() >= credit);
+        }
+    }
+
+    #[test]
+    fn zero_rate_and_overflow() {
+        let zero = TokenCreditRate::default();
+        assert_eq!(zero.credit_for(Token::from_whole(1)), Some(Credit::ZERO));
+        assert_eq!(zero.token_for(Credit::from_whole(1)), None);
+        assert_eq!(zero.token_to_buy(Credit::from_whole(1)), None);
+
+        let rate = TokenCreditRate::from_credits_per_token(2);
+        assert_eq!(rate.credit_for(Token::from_atto(U256::MAX)), None);
+        let half_max = Token::from_atto(U256::MAX / U256::from(2));
+        assert_eq!(rate.token_for(Credit::from_atto(U256::MAX)), Some(half_max));
+        let tiny = TokenCreditRate::from_scaled(U256::from(1));
+        assert_eq!(tiny.token_to_buy(Credit::from_atto(U256::MAX)), None);
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::fmt;
+
+use alloy_primitives::{Address, Bytes};
+use alloy_sol_types::SolCall;
+
+/// The result of executing a call against a facade.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Execution {
+    /// The raw ABI-encoded return data.
+    pub output: Bytes,
+    /// Gas used by the call, or zero for read-only calls the executor doesn't meter.
+    pub gas_used: u64,
+}
+
+/// A typed call result together with the gas it used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Executed<T> {
+    pub ret: T,
+    pub gas_used: u64,
+}
+
+/// A call that executed but failed, as opposed to e.g. a transport failure.
+///
+/// Executors should return these, wrapped in an `anyhow::Error`, so that drivers can tell which
+/// failures a smaller call might avoid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExecutionError {
+    OutOfGas,
+    /// The call reverted with the given revert data.
+    Reverted(Bytes),
+}
+
+impl ExecutionError {
+    /// Returns the execution error in the chain of `error`, if any.
+    pub fn find(error: &anyhow::Error) -> Option<&Self> {
+        // `downcast_ref` also finds errors attached with `context`, which `chain` doesn't.
+        error
+            .downcast_ref()
+            .or_else(|| error.chain().find_map(|e| e.downcast_ref()))
+    }
+}
+
+impl fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfGas => f.write_str("call ran out of gas"),
+            Self::Reverted(data) if data.is_empty() => f.write_str("call reverted"),
+            Self::Reverted(data) => write!(f, "call reverted with {}", data),
+        }
+    }
+}
+
+impl std::error::Error for ExecutionError {}
+
+/// Executes facade calls, e.g. by sending transactions through an RPC provider or by calling
+/// into a local FVM.
+///
+/// This crate doesn't depend on any transport, so drivers that need to call facades repeatedly
+/// are written against this trait.
+pub trait CallExecutor {
+    /// Executes `calldata` against the contract at `to`, waiting for the call to complete.
+    ///
+    /// Fails with an [`ExecutionError`] if the call ran out of gas or reverted.
+    fn execute(&mut self, to: Address, calldata: Bytes) -> anyhow::Result<Execution>;
+
+    /// Encodes `call`, executes it and decodes the return data.
+    fn call<C: SolCall>(&mut self, to: Address, call: &C) -> anyhow::Result<Executed<C::Return>>
+    where
+        Self: Sized,
+    {
+        let execution = self.execute(to, call.abi_encode().into())?;
+        let ret = C::abi_decode_returns(&execution.output, true)?;
+        Ok(Executed {
+            ret,
+            gas_used: execution.gas_used,
+        })
+    }
+}
+
+impl<E: CallExecutor + ?Sized> CallExecutor for &mut E {
+    fn execute(&mut self, to: Address, calldata: Bytes) -> anyhow::Result<Execution> {
+        (**self).execute(to, calldata)
+    }
+}
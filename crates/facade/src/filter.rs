@@ -0,0 +1,369 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! `eth_getLogs` filter objects.
+//!
+//! [`Filter`] is the untyped JSON-RPC filter. [`EventFilter`] wraps it in a typed builder for the
+//! events of one facade, and the facade modules add methods for the indexed event fields, e.g.
+//! `blobs::filter()`, `machine::filter()` and `rewards::filter()`.
+
+use std::fmt;
+
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::{sol_data, EventTopic};
+use anyhow::bail;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+
+/// A block number or tag used as a filter bound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockNumberOrTag {
+    Number(u64),
+    Earliest,
+    Latest,
+    Pending,
+    Safe,
+    Finalized,
+}
+
+impl From<u64> for BlockNumberOrTag {
+    fn from(value: u64) -> Self {
+        BlockNumberOrTag::Number(value)
+    }
+}
+
+impl fmt::Display for BlockNumberOrTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockNumberOrTag::Number(n) => write!(f, "{:#x}", n),
+            BlockNumberOrTag::Earliest => f.write_str("earliest"),
+            BlockNumberOrTag::Latest => f.write_str("latest"),
+            BlockNumberOrTag::Pending => f.write_str("pending"),
+            BlockNumberOrTag::Safe => f.write_str("safe"),
+            BlockNumberOrTag::Finalized => f.write_str("finalized"),
+        }
+    }
+}
+
+/// A topic position of a log. Logs have at most four topics, the first being the event
+/// signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TopicIndex {
+    Signature,
+    First,
+    Second,
+    Third,
+}
+
+impl TopicIndex {
+    pub fn as_usize(self) -> usize {
+        self as usize
+    }
+}
+
+impl TryFrom<usize> for TopicIndex {
+    type Error = anyhow::Error;
+
+    fn try_from(index: usize) -> anyhow::Result<Self> {
+        match index {
+            0 => Ok(Self::Signature),
+            1 => Ok(Self::First),
+            2 => Ok(Self::Second),
+            3 => Ok(Self::Third),
+            _ => bail!("topic index {} is out of range; logs have at most 4 topics", index),
+        }
+    }
+}
+
+/// A JSON-RPC log filter.
+///
+/// Each topic position holds a set of alternatives; an empty set is a wildcard.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Filter {
+    pub addresses: Vec<Address>,
+    pub from_block: Option<BlockNumberOrTag>,
+    pub to_block: Option<BlockNumberOrTag>,
+    pub topics: [Vec<B256>; 4],
+}
+
+impl Filter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an emitting contract address.
+    pub fn address(mut self, address: Address) -> Self {
+        if !self.addresses.contains(&address) {
+            self.addresses.push(address);
+        }
+        self
+    }
+
+    pub fn from_block(mut self, block: impl Into<BlockNumberOrTag>) -> Self {
+        self.from_block = Some(block.into());
+        self
+    }
+
+    pub fn to_block(mut self, block: impl Into<BlockNumberOrTag>) -> Self {
+        self.to_block = Some(block.into());
+        self
+    }
+
+    /// Sets an inclusive block range.
+    pub fn block_range(
+        self,
+        from: impl Into<BlockNumberOrTag>,
+        to: impl Into<BlockNumberOrTag>,
+    ) -> Self {
+        self.from_block(from).to_block(to)
+    }
+
+    /// Adds an alternative for the topic at `index`.
+    pub fn topic(mut self, index: TopicIndex, value: B256) -> Self {
+        let values = &mut self.topics[index.as_usize()];
+        if !values.contains(&value) {
+            values.push(value);
+        }
+        self
+    }
+
+    /// Adds an event signature hash alternative to topic 0.
+    pub fn event_signature(self, hash: B256) -> Self {
+        self.topic(TopicIndex::Signature, hash)
+    }
+
+    /// Returns the filter as a JSON-RPC filter object, suitable as the `eth_getLogs` parameter.
+    pub fn to_json(&self) -> Value {
+        let mut obj = serde_json::Map::new();
+        match self.addresses.as_slice() {
+            [] => {}
+            [address] => {
+                obj.insert("address".into(), json!(address));
+            }
+            addresses => {
+                obj.insert("address".into(), json!(addresses));
+            }
+        }
+        if let Some(block) = self.from_block {
+            obj.insert("fromBlock".into(), json!(block.to_string()));
+        }
+        if let Some(block) = self.to_block {
+            obj.insert("toBlock".into(), json!(block.to_string()));
+        }
+        // Trailing wildcards are dropped; the node treats missing positions as wildcards.
+        let len = self.topics.iter().rposition(|t| !t.is_empty()).map_or(0, |i| i + 1);
+        let topics: Vec<Value> = self.topics[..len]
+            .iter()
+            .map(|values| match values.as_slice() {
+                [] => Value::Null,
+                [value] => json!(value),
+                values => json!(values),
+            })
+            .collect();
+        obj.insert("topics".into(), Value::Array(topics));
+        Value::Object(obj)
+    }
+}
+
+impl Serialize for Filter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+/// The events of a facade that an [`EventFilter`] can select.
+pub trait FilterEvent: Copy + PartialEq + 'static {
+    /// All events, matched when none are selected.
+    const ALL: &'static [Self];
+
+    fn signature_hash(&self) -> B256;
+}
+
+/// Typed `eth_getLogs` filter builder for the events of one facade.
+///
+/// Facade modules add methods for their indexed fields on top of [`topic`](Self::topic).
+/// Repeated calls to the same method add alternatives (logical OR). If no events are selected,
+/// all events in [`FilterEvent::ALL`] match.
+#[derive(Clone, Debug)]
+pub struct EventFilter<K> {
+    inner: Filter,
+    events: Vec<K>,
+}
+
+impl<K> Default for EventFilter<K> {
+    fn default() -> Self {
+        Self {
+            inner: Filter::default(),
+            events: Vec::new(),
+        }
+    }
+}
+
+impl<K: FilterEvent> EventFilter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts logs to those emitted by `address`.
+    pub fn address(mut self, address: Address) -> Self {
+        self.inner = self.inner.address(address);
+        self
+    }
+
+    /// Adds an alternative for an indexed field.
+    pub fn topic(mut self, index: TopicIndex, value: B256) -> Self {
+        self.inner = self.inner.topic(index, value);
+        self
+    }
+
+    /// Matches only the given events.
+    pub fn events(mut self, events: impl IntoIterator<Item = K>) -> Self {
+        for event in events {
+            if !self.events.contains(&event) {
+                self.events.push(event);
+            }
+        }
+        self
+    }
+
+    pub fn from_block(mut self, block: impl Into<BlockNumberOrTag>) -> Self {
+        self.inner = self.inner.from_block(block);
+        self
+    }
+
+    pub fn to_block(mut self, block: impl Into<BlockNumberOrTag>) -> Self {
+        self.inner = self.inner.to_block(block);
+        self
+    }
+
+    /// Sets an inclusive block range.
+    pub fn block_range(
+        self,
+        from: impl Into<BlockNumberOrTag>,
+        to: impl Into<BlockNumberOrTag>,
+    ) -> Self {
+        self.from_block(from).to_block(to)
+    }
+
+    /// Returns the untyped filter.
+    pub fn build(&self) -> Filter {
+        let events = if self.events.is_empty() {
+            K::ALL
+        } else {
+            &self.events[..]
+        };
+        events
+            .iter()
+            .fold(self.inner.clone(), |f, e| f.event_signature(e.signature_hash()))
+    }
+
+    /// Returns the JSON-RPC filter object.
+    pub fn to_json(&self) -> Value {
+        self.build().to_json()
+    }
+}
+
+impl<K: FilterEvent> From<EventFilter<K>> for Filter {
+    fn from(value: EventFilter<K>) -> Self {
+        value.build()
+    }
+}
+
+impl<K: FilterEvent> Serialize for EventFilter<K> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.build().serialize(serializer)
+    }
+}
+
+/// Encodes an indexed `address` as a topic (left-padded to 32 bytes).
+pub(crate) fn address_topic(address: &Address) -> B256 {
+    <sol_data::Address as EventTopic>::encode_topic(address).0
+}
+
+/// Encodes an indexed `uint8` (or a `uint8`-backed enum) as a topic.
+pub(crate) fn u8_topic(value: u8) -> B256 {
+    <sol_data::Uint<8> as EventTopic>::encode_topic(&value).0
+}
+
+/// Encodes an indexed `uint64` as a topic.
+pub(crate) fn u64_topic(value: u64) -> B256 {
+    <sol_data::Uint<64> as EventTopic>::encode_topic(&value).0
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256, U256};
+    use alloy_sol_types::SolEvent;
+    use serde_json::json;
+
+    use super::*;
+    use crate::rewards::{self, RewardsClaimed};
+
+    #[test]
+    fn topic_index_is_bounded() {
+        assert_eq!(TopicIndex::try_from(3).unwrap(), TopicIndex::Third);
+        assert_eq!(TopicIndex::try_from(1).unwrap().as_usize(), 1);
+        assert!(TopicIndex::try_from(4).is_err());
+    }
+
+    #[test]
+    fn filter_to_json() {
+        let a = address!("0000000000000000000000000000000000000001");
+        let b = address!("0000000000000000000000000000000000000002");
+        let t = B256::repeat_byte(0xaa);
+        let filter = Filter::new()
+            .address(a)
+            .address(a)
+            .block_range(16, BlockNumberOrTag::Latest)
+            .topic(TopicIndex::Second, t)
+            .topic(TopicIndex::Second, t);
+        assert_eq!(
+            filter.to_json(),
+            json!({
+                "address": a,
+                "fromBlock": "0x10",
+                "toBlock": "latest",
+                "topics": [null, null, t],
+            })
+        );
+        let filter = filter.address(b).topic(TopicIndex::Second, B256::ZERO);
+        assert_eq!(filter.to_json()["address"], json!([a, b]));
+        assert_eq!(filter.to_json()["topics"][2], json!([t, B256::ZERO]));
+        assert_eq!(Filter::new().to_json(), json!({ "topics": [] }));
+    }
+
+    #[test]
+    fn rewards_filter_pads_indexed_fields() {
+        let validator = address!("00000000000000000000000000000000000000ff");
+        let filter = rewards::filter()
+            .checkpoint_height(0x1234)
+            .validator(validator)
+            .from_block(1);
+        assert_eq!(
+            filter.to_json(),
+            json!({
+                "fromBlock": "0x1",
+                "topics": [
+                    RewardsClaimed::SIGNATURE_HASH,
+                    b256!("0000000000000000000000000000000000000000000000000000000000001234"),
+                    b256!("00000000000000000000000000000000000000000000000000000000000000ff"),
+                ],
+            })
+        );
+        assert_eq!(serde_json::to_value(&filter).unwrap(), filter.to_json());
+        assert_eq!(Filter::from(filter.clone()), filter.build());
+    }
+
+    #[test]
+    fn rewards_claimed_decodes_indexed_fields() {
+        let event = RewardsClaimed {
+            checkpointHeight: 7,
+            validator: address!("0000000000000000000000000000000000000003"),
+            amount: U256::from(5),
+        };
+        let log = event.encode_log_data();
+        assert_eq!(log.topics()[1], u64_topic(7));
+        assert_eq!(log.topics()[2], address_topic(&event.validator));
+        assert_eq!(RewardsClaimed::decode_log_data(&log, true).unwrap(), event);
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::fmt;
+use std::str::FromStr;
+
+use alloy_primitives::{hex, B256};
+use anyhow::{anyhow, bail};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Lowercase RFC 4648 alphabet, as used by `Base32.sol`.
+const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Length of the base32 encoding of 32 bytes, without padding.
+const ENCODED_LEN: usize = 52;
+
+macro_rules! base32_hash {
+    ($(#[$meta:meta])* $name:ident, $what:literal) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(B256);
+
+        impl $name {
+            pub const fn new(bytes: B256) -> Self {
+                $name(bytes)
+            }
+
+            pub const fn as_b256(&self) -> &B256 {
+                &self.0
+            }
+
+            pub fn is_zero(&self) -> bool {
+                self.0.is_zero()
+            }
+
+            /// Returns the canonical lowercase base32 string, without padding.
+            pub fn to_base32(&self) -> String {
+                encode(&self.0)
+            }
+
+            /// Parses a canonical lowercase base32 string.
+            pub fn from_base32(s: &str) -> anyhow::Result<Self> {
+                decode(s)
+                    .map($name)
+                    .map_err(|e| anyhow!(concat!("invalid ", $what, " {:?}: {}"), s, e))
+            }
+        }
+
+        impl From<B256> for $name {
+            fn from(value: B256) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for B256 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.to_base32())
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, concat!(stringify!($name), "({})"), self.to_base32())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = anyhow::Error;
+
+            /// Parses base32, or `0x`-prefixed hex as found in facade ABIs.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                if s.starts_with("0x") {
+                    let bytes: [u8; 32] = hex::decode_to_array(s)
+                        .map_err(|e| anyhow!(concat!("invalid ", $what, " {:?}: {}"), s, e))?;
+                    Ok($name(B256::from(bytes)))
+                } else {
+                    Self::from_base32(s)
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.to_base32())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+base32_hash!(
+    /// A blake3 blob hash, e.g. `blobHash`, `metadataHash` or `recoveryHash` in the facades.
+    ///
+    /// Displays as the lowercase base32 string used by `BlobTypes.sol`, `BucketTypes.sol` and
+    /// the Recall tooling.
+    BlobHash,
+    "blob hash"
+);
+
+base32_hash!(
+    /// An Iroh node ID, e.g. `source` in the facades.
+    ///
+    /// Displays as the lowercase base32 string used by `BlobTypes.sol` and the Recall tooling.
+    NodeId,
+    "node ID"
+);
+
+fn encode(bytes: &B256) -> String {
+    let mut out = String::with_capacity(ENCODED_LEN);
+    let mut value: u16 = 0;
+    let mut bits = 0;
+    for &byte in bytes.iter() {
+        value = (value << 8) | u16::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[usize::from((value >> bits) & 0x1f)] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[usize::from((value << (5 - bits)) & 0x1f)] as char);
+    }
+    out
+}
+
+fn decode(s: &str) -> anyhow::Result<B256> {
+    if s.len() != ENCODED_LEN {
+        bail!("expected {} base32 characters, got {}", ENCODED_LEN, s.len());
+    }
+    let mut out = [0u8; 32];
+    let mut index = 0;
+    let mut value: u16 = 0;
+    let mut bits = 0;
+    for c in s.bytes() {
+        let digit = match c {
+            b'a'..=b'z' => c - b'a',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => bail!("invalid base32 character {:?}", c as char),
+        };
+        value = (value << 5) | u16::from(digit);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out[index] = (value >> bits) as u8;
+            index += 1;
+        }
+    }
+    // The last character carries unused low bits, which must be zero in the canonical form.
+    if value & ((1 << bits) - 1) != 0 {
+        bail!("non-canonical trailing bits");
+    }
+    Ok(B256::from(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::b256;
+
+    use super::*;
+
+    /// From Python's `base64.b32encode(bytes).decode().lower().rstrip("=")`.
+    const VECTORS: [(B256, &str); 4] = [
+        (B256::ZERO, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+        (
+            b256!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"),
+            "aaaqeayeaudaocajbifqydiob4ibceqtcqkrmfyydenbwha5dypq",
+        ),
+        (
+            b256!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"),
+            "777777777777777777777777777777777777777777777777777q",
+        ),
+        (
+            b256!("2ed6b4c1ae9f8249477df9193d20e75474c152748e0f2a2cc224ead2e1c8769d"),
+            "f3lljqnot6besr357emt2ihhkr2mcuturyhsulgcetvnfyoio2oq",
+        ),
+    ];
+
+    #[test]
+    fn known_answers() {
+        for (bytes, base32) in VECTORS {
+            assert_eq!(BlobHash::new(bytes).to_base32(), base32);
+            assert_eq!(BlobHash::from_base32(base32).unwrap(), BlobHash::new(bytes));
+            assert_eq!(NodeId::from_base32(base32).unwrap().as_b256(), &bytes);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let hash = BlobHash::new(B256::repeat_byte(0x5a));
+        assert_eq!(hash.to_string().parse::<BlobHash>().unwrap(), hash);
+        assert_eq!(hash.as_b256().to_string().parse::<BlobHash>().unwrap(), hash);
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json, format!("\"{}\"", hash));
+        assert_eq!(serde_json::from_str::<BlobHash>(&json).unwrap(), hash);
+        assert_eq!(format!("{:?}", hash), format!("BlobHash({})", hash));
+    }
+
+    #[test]
+    fn invalid_characters() {
+        let upper = "AAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPQ";
+        for s in [upper, "aaaqeayeaudaocajbifqydiob4ibceqtcqkrmfyydenbwha5dyp1"] {
+            let err = BlobHash::from_base32(s).err().unwrap();
+            assert!(err.to_string().contains("invalid base32 character"), "{}", err);
+        }
+        let padded = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa==";
+        assert!(BlobHash::from_base32(padded).is_err());
+    }
+
+    #[test]
+    fn wrong_length() {
+        for s in ["", "aaaa", &"a".repeat(51), &"a".repeat(53), &"a".repeat(56)] {
+            let err = NodeId::from_base32(s).err().unwrap();
+            assert!(err.to_string().contains("expected 52 base32 characters"), "{}", err);
+        }
+        assert!("0x00".parse::<BlobHash>().is_err());
+    }
+
+    #[test]
+    fn non_canonical_trailing_bits() {
+        // "7" sets the low bits that the final character doesn't use.
+        let s = "7777777777777777777777777777777777777777777777777777";
+        let err = BlobHash::from_base32(s).err().unwrap();
+        assert!(err.to_string().contains("non-canonical trailing bits"), "{}", err);
+    }
+}
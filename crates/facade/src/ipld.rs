@@ -0,0 +1,138 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Codecs for the opaque `bytes` fields of facade events.
+//!
+//! The actors emit metadata as an IPLD-encoded `HashMap<String, String>`, and object keys as
+//! their raw bytes.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::anyhow;
+
+use crate::common::KeyValue;
+
+/// Decodes IPLD-encoded metadata, e.g. `ObjectAdded.metadata` or `MachineCreated.metadata`.
+///
+/// Empty bytes decode to empty metadata.
+pub fn decode_metadata(bytes: &[u8]) -> anyhow::Result<HashMap<String, String>> {
+    if bytes.is_empty() {
+        return Ok(HashMap::new());
+    }
+    fvm_ipld_encoding::from_slice(bytes).map_err(|e| anyhow!("invalid metadata: {}", e))
+}
+
+/// Encodes metadata the way the actors do. Entries are written in key order, so equal metadata
+/// always encodes to equal bytes.
+pub fn encode_metadata<K, V>(metadata: impl IntoIterator<Item = (K, V)>) -> anyhow::Result<Vec<u8>>
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    let metadata: BTreeMap<String, String> = metadata
+        .into_iter()
+        .map(|(k, v)| (k.into(), v.into()))
+        .collect();
+    fvm_ipld_encoding::to_vec(&metadata).map_err(|e| anyhow!("failed to encode metadata: {}", e))
+}
+
+/// Decodes IPLD-encoded metadata into key-value pairs sorted by key, e.g. as `bucket::KeyValue`.
+pub fn decode_key_values<T: From<KeyValue>>(bytes: &[u8]) -> anyhow::Result<Vec<T>> {
+    let mut metadata: Vec<KeyValue> = decode_metadata(bytes)?
+        .into_iter()
+        .map(|(key, value)| KeyValue { key, value })
+        .collect();
+    metadata.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(metadata.into_iter().map(T::from).collect())
+}
+
+/// Encodes key-value pairs, e.g. `bucket::KeyValue`, as metadata. If a key occurs more than once,
+/// the last value wins.
+pub fn encode_key_values<T: Clone + Into<KeyValue>>(metadata: &[T]) -> anyhow::Result<Vec<u8>> {
+    encode_metadata(metadata.iter().cloned().map(|kv| {
+        let kv: KeyValue = kv.into();
+        (kv.key, kv.value)
+    }))
+}
+
+/// Decodes an object key, e.g. `ObjectDeleted.key`, failing if it isn't valid UTF-8.
+pub fn decode_key(bytes: &[u8]) -> anyhow::Result<String> {
+    String::from_utf8(bytes.to_vec()).map_err(|e| {
+        anyhow!(
+            "object key 0x{} is not valid UTF-8: {}",
+            alloy_primitives::hex::encode(bytes),
+            e.utf8_error()
+        )
+    })
+}
+
+/// Encodes an object key as event bytes.
+pub fn encode_key(key: &str) -> Vec<u8> {
+    key.as_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_round_trip() {
+        let metadata = HashMap::from([
+            ("b".to_string(), "2".to_string()),
+            ("a".to_string(), "".to_string()),
+            ("ключ".to_string(), "значение".to_string()),
+        ]);
+        let bytes = encode_metadata(metadata.clone()).unwrap();
+        assert_eq!(decode_metadata(&bytes).unwrap(), metadata);
+        // Key order doesn't change the encoding.
+        let mut reversed: Vec<_> = metadata.into_iter().collect();
+        reversed.sort();
+        reversed.reverse();
+        assert_eq!(encode_metadata(reversed).unwrap(), bytes);
+    }
+
+    #[test]
+    fn empty_metadata() {
+        assert!(decode_metadata(&[]).unwrap().is_empty());
+        let bytes = encode_metadata(Vec::<(String, String)>::new()).unwrap();
+        assert!(decode_metadata(&bytes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn key_values_round_trip_sorted() {
+        let metadata = vec![KeyValue::new("z", "1"), KeyValue::new("a", "2")];
+        let bytes = encode_key_values(&metadata).unwrap();
+        let decoded: Vec<KeyValue> = decode_key_values(&bytes).unwrap();
+        assert_eq!(decoded, [KeyValue::new("a", "2"), KeyValue::new("z", "1")]);
+    }
+
+    #[test]
+    fn duplicate_keys_keep_last_value() {
+        let metadata = vec![KeyValue::new("a", "1"), KeyValue::new("a", "2")];
+        let decoded: Vec<KeyValue> =
+            decode_key_values(&encode_key_values(&metadata).unwrap()).unwrap();
+        assert_eq!(decoded, [KeyValue::new("a", "2")]);
+    }
+
+    #[test]
+    fn invalid_metadata() {
+        let err = decode_metadata(&[0xff, 0x00]).err().unwrap();
+        assert!(err.to_string().starts_with("invalid metadata: "), "{}", err);
+    }
+
+    #[test]
+    fn key_round_trip() {
+        for key in ["", "a/b.txt", "日本語/ファイル"] {
+            assert_eq!(decode_key(&encode_key(key)).unwrap(), key);
+        }
+    }
+
+    #[test]
+    fn non_utf8_key() {
+        let err = decode_key(&[0x61, 0xff, 0x62]).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "object key 0x61ff62 is not valid UTF-8: invalid utf-8 sequence of 1 bytes from index 1"
+        );
+    }
+}
@@ -5,6 +5,18 @@
 
 pub use alloy_primitives as primitives;
 
+mod abi;
+pub mod amount;
+pub mod clock;
+pub mod common;
+pub mod executor;
+pub mod filter;
+pub mod hash;
+pub mod ipld;
+pub mod metadata;
+mod persist;
+pub mod rewards;
+pub mod subscription;
 pub mod types;
 
 #[cfg(feature = "blob-reader")]
@@ -15,6 +27,11 @@ pub mod blob_reader {
     pub type ReadRequestClosed = crate::blobreader_facade::iblobreaderfacade::IBlobReaderFacade::ReadRequestClosed;
     pub type ReadRequestOpened = crate::blobreader_facade::iblobreaderfacade::IBlobReaderFacade::ReadRequestOpened;
     pub type ReadRequestPending = crate::blobreader_facade::iblobreaderfacade::IBlobReaderFacade::ReadRequestPending;
+
+    mod plan;
+    pub use plan::{Assembler, ReadPlan, ReadRange};
+    mod tracker;
+    pub use tracker::{ReadRequest, ReadStatus, ReadTracker};
 }
 
 #[cfg(feature = "blobs")]
@@ -45,6 +62,22 @@ pub mod blobs {
     pub type Blob = crate::blobs_facade::iblobsfacade::IBlobsFacade::Blob;
     pub type SubnetStats = crate::blobs_facade::iblobsfacade::IBlobsFacade::SubnetStats;
     pub type TrimBlobExpiries = crate::blobs_facade::iblobsfacade::IBlobsFacade::TrimBlobExpiries;
+
+    mod filter;
+    pub use filter::{filter, BlobsFilter, EventKind};
+    pub mod lazy;
+    mod ledger;
+    pub use ledger::{Discrepancy, Ledger, Usage};
+    #[cfg(feature = "config")]
+    mod request;
+    #[cfg(feature = "config")]
+    pub use request::BlobRequest;
+    mod stats;
+    pub use stats::{StatsDelta, StatsMetrics, StatsRates};
+    mod status;
+    pub use status::{BlobState, Lifecycle, Status, Transition};
+    mod trim;
+    pub use trim::{TrimCursor, TrimDriver, TrimProgress};
 }
 
 #[cfg(feature = "bucket")]
@@ -83,6 +116,27 @@ pub mod bucket {
     pub type Query = crate::bucket_facade::ibucketfacade::IBucketFacade::Query;
     pub type Object = crate::bucket_facade::ibucketfacade::IBucketFacade::Object;
     pub type ObjectState = crate::bucket_facade::ibucketfacade::IBucketFacade::ObjectState;
+
+    mod codec;
+    pub use codec::{decode_metadata, encode_metadata};
+    mod index;
+    pub use index::{IndexCheckpoint, IndexedObject, LogPosition, ObjectIndex};
+    pub mod lazy;
+    mod list;
+    pub use list::{list_objects, ListEntry, ObjectLister};
+    mod manifest;
+    pub use manifest::{
+        export_manifest, read_manifest, write_manifest, ManifestEntry, ManifestFormat,
+        ManifestImporter,
+    };
+    mod model;
+    pub use model::{BucketModel, DEFAULT_DELIMITER};
+    mod select;
+    pub use select::{CompareOp, MatchingObjects, MetadataFilter, Predicate};
+    mod sync;
+    pub use sync::{scan_dir, LocalFile, SyncAction, SyncPlan, SyncPlanner};
+    mod walk;
+    pub use walk::{Directory, DiskUsage, TreeNode, TreeWalker, WalkOrder};
 }
 
 #[cfg(feature = "config")]
@@ -92,6 +146,9 @@ pub mod config {
     pub type Events = crate::config_facade::iconfigfacade::IConfigFacade::IConfigFacadeEvents;
     pub type ConfigAdminSet = crate::config_facade::iconfigfacade::IConfigFacade::ConfigAdminSet;
     pub type ConfigSet = crate::config_facade::iconfigfacade::IConfigFacade::ConfigSet;
+
+    mod snapshot;
+    pub use snapshot::SubnetConfig;
 }
 
 #[cfg(feature = "credit")]
@@ -132,6 +189,9 @@ pub mod credit {
     pub type Approval = crate::credit_facade::icreditfacade::ICreditFacade::Approval;
     pub type CreditApproval = crate::credit_facade::icreditfacade::ICreditFacade::CreditApproval;
     pub type TtlStatus = crate::credit_facade::icreditfacade::ICreditFacade::TtlStatus;
+
+    mod units;
+    pub use units::{Credit, Token, TokenCreditRate};
 }
 
 #[cfg(feature = "gas")]
@@ -166,6 +226,11 @@ pub mod machine {
     pub type Machine = crate::machine_facade::imachinefacade::IMachineFacade::Machine;
     pub type Kind = crate::machine_facade::imachinefacade::IMachineFacade::Kind;
     pub type KeyValue = crate::machine_facade::imachinefacade::IMachineFacade::KeyValue;
+
+    mod codec;
+    pub use codec::{decode_metadata, encode_metadata};
+    mod filter;
+    pub use filter::{filter, EventKind, MachineFilter};
 }
 
 #[cfg(feature = "timehub")]
@@ -0,0 +1,56 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::HashMap;
+
+use alloy_primitives::{Address, Bytes};
+
+use super::{KeyValue, MachineCreated};
+use crate::ipld;
+
+/// Decodes IPLD-encoded event metadata into key-value pairs, sorted by key.
+pub fn decode_metadata(bytes: &[u8]) -> anyhow::Result<Vec<KeyValue>> {
+    ipld::decode_key_values(bytes)
+}
+
+/// Encodes key-value pairs as event metadata. If a key occurs more than once, the last value wins.
+pub fn encode_metadata(metadata: &[KeyValue]) -> anyhow::Result<Bytes> {
+    Ok(ipld::encode_key_values(metadata)?.into())
+}
+
+impl MachineCreated {
+    pub fn from_parts(kind: u8, owner: Address, metadata: &[KeyValue]) -> anyhow::Result<Self> {
+        Ok(Self {
+            kind,
+            owner,
+            metadata: encode_metadata(metadata)?,
+        })
+    }
+
+    pub fn metadata_map(&self) -> anyhow::Result<HashMap<String, String>> {
+        ipld::decode_metadata(&self.metadata)
+    }
+
+    pub fn metadata_key_values(&self) -> anyhow::Result<Vec<KeyValue>> {
+        decode_metadata(&self.metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn machine_created_round_trip() {
+        let metadata = [KeyValue {
+            key: "alias".to_string(),
+            value: "photos".to_string(),
+        }];
+        let event = MachineCreated::from_parts(0, Address::repeat_byte(1), &metadata).unwrap();
+        let decoded = event.metadata_key_values().unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!((decoded[0].key.as_str(), decoded[0].value.as_str()), ("alias", "photos"));
+        assert_eq!(event.metadata_map().unwrap()["alias"], "photos");
+        assert!(decode_metadata(&encode_metadata(&[]).unwrap()).unwrap().is_empty());
+    }
+}
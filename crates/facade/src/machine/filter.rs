@@ -0,0 +1,69 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::SolEvent;
+
+use super::{Kind, MachineCreated, MachineInitialized};
+use crate::filter::{address_topic, u8_topic, EventFilter, FilterEvent, TopicIndex};
+
+/// A machine facade event kind, used to select events in a [`MachineFilter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    MachineCreated,
+    MachineInitialized,
+}
+
+impl FilterEvent for EventKind {
+    const ALL: &'static [Self] = &[EventKind::MachineCreated, EventKind::MachineInitialized];
+
+    fn signature_hash(&self) -> B256 {
+        match self {
+            EventKind::MachineCreated => MachineCreated::SIGNATURE_HASH,
+            EventKind::MachineInitialized => MachineInitialized::SIGNATURE_HASH,
+        }
+    }
+}
+
+/// Typed `eth_getLogs` filter builder for machine facade events.
+pub type MachineFilter = EventFilter<EventKind>;
+
+/// Returns a log filter builder for machine facade events.
+///
+/// Both events index the machine `kind` as their first topic. Only `MachineCreated` indexes
+/// `owner`, so filtering on an owner excludes `MachineInitialized` logs.
+pub fn filter() -> MachineFilter {
+    MachineFilter::default()
+}
+
+impl MachineFilter {
+    /// Matches logs with the given indexed machine kind.
+    pub fn kind(self, kind: Kind) -> Self {
+        self.topic(TopicIndex::First, u8_topic(Kind::into(kind)))
+    }
+
+    /// Matches `MachineCreated` logs with the given indexed owner.
+    pub fn owner(self, owner: Address) -> Self {
+        self.topic(TopicIndex::Second, address_topic(&owner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pads_kind_and_owner() {
+        let owner = Address::repeat_byte(0x22);
+        let topics = filter()
+            .events([EventKind::MachineCreated])
+            .kind(Kind::from(1))
+            .owner(owner)
+            .build()
+            .topics;
+        assert_eq!(topics[0], [MachineCreated::SIGNATURE_HASH]);
+        assert_eq!(topics[1], [B256::with_last_byte(1)]);
+        assert_eq!(topics[2][0].as_slice()[12..], *owner.as_slice());
+        assert!(topics[3].is_empty());
+    }
+}
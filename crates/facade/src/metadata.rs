@@ -0,0 +1,380 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use anyhow::bail;
+
+use crate::ipld;
+
+/// Size limits on metadata, checked by the actors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetadataLimits {
+    pub max_entries: usize,
+    /// In bytes.
+    pub max_key_size: usize,
+    /// In bytes.
+    pub max_value_size: usize,
+}
+
+impl MetadataLimits {
+    /// The limits of the bucket actor on object metadata.
+    pub const BUCKET: Self = Self {
+        max_entries: 20,
+        max_key_size: 32,
+        max_value_size: 128,
+    };
+
+    /// No limits.
+    pub const NONE: Self = Self {
+        max_entries: usize::MAX,
+        max_key_size: usize::MAX,
+        max_value_size: usize::MAX,
+    };
+}
+
+impl Default for MetadataLimits {
+    fn default() -> Self {
+        Self::BUCKET
+    }
+}
+
+/// Validated metadata for the `KeyValue[]` parameters of `addObject`, `updateObjectMetadata` and
+/// `createBucket`.
+///
+/// Keys are unique and not empty, and entries are sorted by key. Values may be empty, which
+/// `updateObjectMetadata` treats as removing the key.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metadata {
+    entries: BTreeMap<String, String>,
+    limits: MetadataLimits,
+}
+
+impl Metadata {
+    /// Returns empty metadata checked against [`MetadataLimits::BUCKET`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limits(limits: MetadataLimits) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            limits,
+        }
+    }
+
+    /// Builds metadata from key-value pairs, checked against [`MetadataLimits::BUCKET`]. Fails
+    /// with all problems found, including duplicate keys.
+    pub fn from_pairs<K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> anyhow::Result<Self>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self::from_pairs_with_limits(pairs, MetadataLimits::BUCKET)
+    }
+
+    pub fn from_pairs_with_limits<K, V>(
+        pairs: impl IntoIterator<Item = (K, V)>,
+        limits: MetadataLimits,
+    ) -> anyhow::Result<Self>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut metadata = Self::with_limits(limits);
+        let mut problems = Vec::new();
+        for (key, value) in pairs {
+            let (key, value) = (key.into(), value.into());
+            problems.extend(metadata.check_entry(&key, &value));
+            if metadata.entries.contains_key(&key) {
+                problems.push(format!("duplicate key {:?}", key));
+            }
+            metadata.entries.insert(key, value);
+        }
+        if metadata.entries.len() > limits.max_entries {
+            problems.push(format!(
+                "{} entries exceed the maximum of {}",
+                metadata.entries.len(),
+                limits.max_entries
+            ));
+        }
+        if !problems.is_empty() {
+            bail!("invalid metadata: {}", problems.join("; "));
+        }
+        Ok(metadata)
+    }
+
+    /// Decodes IPLD-encoded event metadata, checked against [`MetadataLimits::NONE`].
+    pub fn from_ipld(bytes: &[u8]) -> anyhow::Result<Self> {
+        Self::from_pairs_with_limits(ipld::decode_metadata(bytes)?, MetadataLimits::NONE)
+    }
+
+    pub fn to_ipld(&self) -> anyhow::Result<Vec<u8>> {
+        ipld::encode_metadata(self.iter())
+    }
+
+    pub fn limits(&self) -> MetadataLimits {
+        self.limits
+    }
+
+    /// Inserts or replaces an entry, returning the previous value.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> anyhow::Result<Option<String>> {
+        let (key, value) = (key.into(), value.into());
+        if let Some(problem) = self.check_entry(&key, &value).into_iter().next() {
+            bail!("invalid metadata: {}", problem);
+        }
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.limits.max_entries {
+            bail!("invalid metadata: more than {} entries", self.limits.max_entries);
+        }
+        Ok(self.entries.insert(key, value))
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.entries.remove(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over entries in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    fn check_entry(&self, key: &str, value: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+        if key.is_empty() {
+            problems.push("empty key".to_string());
+        }
+        if key.len() > self.limits.max_key_size {
+            problems.push(format!(
+                "key {:?} is {} bytes, exceeding the maximum of {}",
+                key,
+                key.len(),
+                self.limits.max_key_size
+            ));
+        }
+        if value.len() > self.limits.max_value_size {
+            problems.push(format!(
+                "value of key {:?} is {} bytes, exceeding the maximum of {}",
+                key,
+                value.len(),
+                self.limits.max_value_size
+            ));
+        }
+        problems
+    }
+}
+
+impl fmt::Display for Metadata {
+    /// Formats as `key=value` pairs separated by commas.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&Metadata> for HashMap<String, String> {
+    fn from(metadata: &Metadata) -> Self {
+        metadata.entries.clone().into_iter().collect()
+    }
+}
+
+impl From<Metadata> for BTreeMap<String, String> {
+    fn from(metadata: Metadata) -> Self {
+        metadata.entries
+    }
+}
+
+macro_rules! key_value_conversions {
+    ($(#[$attr:meta])* $facade:ident) => {
+        $(#[$attr])*
+        impl From<&Metadata> for Vec<crate::$facade::KeyValue> {
+            fn from(metadata: &Metadata) -> Self {
+                metadata
+                    .iter()
+                    .map(|(key, value)| crate::$facade::KeyValue {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    })
+                    .collect()
+            }
+        }
+
+        $(#[$attr])*
+        impl From<Metadata> for Vec<crate::$facade::KeyValue> {
+            fn from(metadata: Metadata) -> Self {
+                (&metadata).into()
+            }
+        }
+
+        $(#[$attr])*
+        impl TryFrom<&[crate::$facade::KeyValue]> for Metadata {
+            type Error = anyhow::Error;
+
+            /// Validates against [`MetadataLimits::BUCKET`].
+            fn try_from(metadata: &[crate::$facade::KeyValue]) -> anyhow::Result<Self> {
+                Self::from_pairs(metadata.iter().map(|kv| (kv.key.clone(), kv.value.clone())))
+            }
+        }
+    };
+}
+
+key_value_conversions!(common);
+key_value_conversions!(#[cfg(feature = "bucket")] bucket);
+key_value_conversions!(#[cfg(feature = "machine")] machine);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(metadata: &Metadata) -> Vec<(&str, &str)> {
+        metadata.iter().collect()
+    }
+
+    #[test]
+    fn builds_from_pairs_in_key_order() {
+        let pairs = [("b", "2"), ("a", "1"), ("B", ""), ("ab", "3")];
+        let metadata = Metadata::from_pairs(pairs).unwrap();
+        // Sorted by key bytes, so uppercase first.
+        assert_eq!(entries(&metadata), [("B", ""), ("a", "1"), ("ab", "3"), ("b", "2")]);
+        assert_eq!((metadata.len(), metadata.get("a"), metadata.get("c")), (4, Some("1"), None));
+        assert_eq!(metadata.limits(), MetadataLimits::BUCKET);
+        assert_eq!(metadata.to_string(), "B=,a=1,ab=3,b=2");
+        assert!(Metadata::from_pairs(Vec::<(String, String)>::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn reports_all_problems() {
+        let long_key = "k".repeat(33);
+        let mut pairs = vec![
+            ("a".to_string(), "1".to_string()),
+            (String::new(), "empty".to_string()),
+            (long_key.clone(), "x".to_string()),
+            ("b".to_string(), "v".repeat(129)),
+            ("a".to_string(), "2".to_string()),
+        ];
+        pairs.extend((0..17).map(|i| (format!("key{:02}", i), String::new())));
+        let err = Metadata::from_pairs(pairs).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "invalid metadata: empty key; key {:?} is 33 bytes, exceeding the maximum of 32; \
+                 value of key \"b\" is 129 bytes, exceeding the maximum of 128; \
+                 duplicate key \"a\"; 21 entries exceed the maximum of 20",
+                long_key
+            )
+        );
+    }
+
+    #[test]
+    fn checks_limits_at_the_boundary() {
+        let key = "k".repeat(32);
+        let value = "v".repeat(128);
+        assert!(Metadata::from_pairs([(key.as_str(), value.as_str())]).is_ok());
+
+        let pairs = |n: usize| (0..n).map(|i| (format!("key{:02}", i), String::new()));
+        assert_eq!(Metadata::from_pairs(pairs(20)).unwrap().len(), 20);
+        assert_eq!(
+            Metadata::from_pairs(pairs(21)).unwrap_err().to_string(),
+            "invalid metadata: 21 entries exceed the maximum of 20"
+        );
+        let unlimited = Metadata::from_pairs_with_limits(pairs(21), MetadataLimits::NONE).unwrap();
+        assert_eq!(unlimited.len(), 21);
+    }
+
+    #[test]
+    fn insert_replaces_at_the_limit() {
+        let limits = MetadataLimits {
+            max_entries: 2,
+            ..MetadataLimits::BUCKET
+        };
+        let mut metadata = Metadata::with_limits(limits);
+        assert_eq!(metadata.insert("a", "1").unwrap(), None);
+        assert_eq!(metadata.insert("b", "2").unwrap(), None);
+        // Replacing doesn't add an entry, so it's allowed at the limit.
+        assert_eq!(metadata.insert("a", "3").unwrap(), Some("1".to_string()));
+        assert_eq!(
+            metadata.insert("c", "4").unwrap_err().to_string(),
+            "invalid metadata: more than 2 entries"
+        );
+        assert_eq!(
+            metadata.insert("", "5").unwrap_err().to_string(),
+            "invalid metadata: empty key"
+        );
+        assert!(metadata.insert("b", "v".repeat(129)).is_err());
+        assert_eq!(entries(&metadata), [("a", "3"), ("b", "2")]);
+
+        assert_eq!(metadata.remove("a"), Some("3".to_string()));
+        assert!(metadata.insert("c", "4").is_ok());
+    }
+
+    #[test]
+    fn round_trips_ipld() {
+        let metadata = Metadata::from_pairs([("type", "text/plain"), ("a", "")]).unwrap();
+        let decoded = Metadata::from_ipld(&metadata.to_ipld().unwrap()).unwrap();
+        assert_eq!(entries(&decoded), entries(&metadata));
+        // Event metadata isn't limited, since the actor already accepted it.
+        assert_eq!(decoded.limits(), MetadataLimits::NONE);
+        let long = Metadata::from_pairs_with_limits([("k", "v".repeat(200))], MetadataLimits::NONE)
+            .unwrap();
+        let decoded = Metadata::from_ipld(&long.to_ipld().unwrap()).unwrap();
+        assert_eq!(decoded.get("k").map(str::len), Some(200));
+        assert!(Metadata::from_ipld(&[0xff]).is_err());
+    }
+
+    #[test]
+    fn converts_maps() {
+        let metadata = Metadata::from_pairs([("b", "2"), ("a", "1")]).unwrap();
+        let map = HashMap::from(&metadata);
+        assert_eq!(map.get("a").map(String::as_str), Some("1"));
+        let tree = BTreeMap::from(metadata);
+        let pairs: Vec<_> = tree.into_iter().collect();
+        assert_eq!(pairs, [("a".into(), "1".into()), ("b".into(), "2".into())]);
+    }
+
+    macro_rules! key_value_tests {
+        ($(#[$attr:meta])* $name:ident, $facade:ident) => {
+            $(#[$attr])*
+            #[test]
+            fn $name() {
+                use crate::$facade::KeyValue;
+
+                let metadata = Metadata::from_pairs([("b", "2"), ("a", "1")]).unwrap();
+                let key_values = Vec::<KeyValue>::from(&metadata);
+                let pairs: Vec<_> = key_values.iter().map(|kv| (&*kv.key, &*kv.value)).collect();
+                assert_eq!(pairs, [("a", "1"), ("b", "2")]);
+                let back = Metadata::try_from(key_values.as_slice()).unwrap();
+                assert_eq!(back, metadata);
+                assert_eq!(Vec::<KeyValue>::from(back).len(), 2);
+
+                let duplicate = [key_values[0].clone(), key_values[0].clone()];
+                let err = Metadata::try_from(&duplicate[..]).unwrap_err();
+                assert_eq!(err.to_string(), "invalid metadata: duplicate key \"a\"");
+            }
+        };
+    }
+
+    key_value_tests!(converts_common_key_values, common);
+    key_value_tests!(#[cfg(feature = "bucket")] converts_bucket_key_values, bucket);
+    key_value_tests!(#[cfg(feature = "machine")] converts_machine_key_values, machine);
+}
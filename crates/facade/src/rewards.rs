@@ -0,0 +1,57 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Events of `ValidatorRewarder.sol`, which is not a facade, so it has no generated bindings.
+
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::SolEvent;
+
+use crate::filter::{address_topic, u64_topic, EventFilter, FilterEvent, TopicIndex};
+
+alloy_sol_types::sol! {
+    /// Emitted when a validator claims its rewards for a checkpoint.
+    #[derive(Debug, PartialEq, Eq)]
+    event RewardsClaimed(
+        uint64 indexed checkpointHeight,
+        address indexed validator,
+        uint256 amount
+    );
+}
+
+/// A validator rewarder event kind, used to select events in a [`RewardsFilter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    RewardsClaimed,
+}
+
+impl FilterEvent for EventKind {
+    const ALL: &'static [Self] = &[EventKind::RewardsClaimed];
+
+    fn signature_hash(&self) -> B256 {
+        match self {
+            EventKind::RewardsClaimed => RewardsClaimed::SIGNATURE_HASH,
+        }
+    }
+}
+
+/// Typed `eth_getLogs` filter builder for validator rewarder events.
+pub type RewardsFilter = EventFilter<EventKind>;
+
+/// Returns a log filter builder for validator rewarder events.
+///
+/// `RewardsClaimed` indexes the checkpoint height and the validator.
+pub fn filter() -> RewardsFilter {
+    RewardsFilter::default()
+}
+
+impl RewardsFilter {
+    /// Matches logs with the given indexed checkpoint height.
+    pub fn checkpoint_height(self, height: u64) -> Self {
+        self.topic(TopicIndex::First, u64_topic(height))
+    }
+
+    /// Matches logs with the given indexed validator.
+    pub fn validator(self, validator: Address) -> Self {
+        self.topic(TopicIndex::Second, address_topic(&validator))
+    }
+}
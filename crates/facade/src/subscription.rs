@@ -0,0 +1,203 @@
+// Copyright 2025 Recall Contributors
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use std::fmt;
+
+use alloy_primitives::{hex, B256};
+use fvm_shared::address::Address;
+
+/// A blob subscription ID, as found in `Subscription.subscriptionId` and `addBlob`.
+///
+/// Blobs added directly use a human-readable ID, or the empty string for the default
+/// subscription. Blobs added through a bucket use `blake3(bucket_address + object_key)` as raw
+/// bytes, which is usually not valid text; see [`bucket_subscription_id`]. These are rendered
+/// as hex.
+///
+/// Note that decoding a `string` with the generated bindings replaces invalid UTF-8 with
+/// `U+FFFD`, which loses the hash. Use [`SubscriptionId::from_bytes`] on the raw ABI bytes
+/// (e.g. from the lazy views) to recover it; a lossy string classifies as
+/// [`Opaque`](SubscriptionId::Opaque).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SubscriptionId {
+    /// The default subscription, i.e. the empty string.
+    Default,
+    /// A human-readable subscription ID.
+    Named(String),
+    /// A bucket-derived subscription ID.
+    Bucket(B256),
+    /// Bytes that are neither readable text nor a bucket-derived ID.
+    Opaque(Vec<u8>),
+}
+
+impl SubscriptionId {
+    /// Returns the subscription ID the bucket at `bucket` uses for the object at `key`.
+    pub fn for_bucket_object(bucket: &Address, key: &[u8]) -> Self {
+        SubscriptionId::Bucket(bucket_subscription_id(bucket, key))
+    }
+
+    /// Classifies raw subscription ID bytes.
+    ///
+    /// The bytes don't say how the ID was made, so this is a heuristic: readable UTF-8 is
+    /// [`Named`](Self::Named), and any other 32 bytes are [`Bucket`](Self::Bucket). A hash that
+    /// happens to be readable UTF-8 is misclassified as named; use
+    /// [`is_for_bucket_object`](Self::is_for_bucket_object) when the bucket and key are known.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        if bytes.is_empty() {
+            return SubscriptionId::Default;
+        }
+        match std::str::from_utf8(bytes) {
+            Ok(s) if is_readable(s) => SubscriptionId::Named(s.to_string()),
+            _ if bytes.len() == 32 => SubscriptionId::Bucket(B256::from_slice(bytes)),
+            _ => SubscriptionId::Opaque(bytes.to_vec()),
+        }
+    }
+
+    /// Classifies a subscription ID decoded as a string.
+    pub fn parse(s: &str) -> Self {
+        Self::from_bytes(s.as_bytes())
+    }
+
+    pub fn is_bucket_derived(&self) -> bool {
+        matches!(self, SubscriptionId::Bucket(_))
+    }
+
+    /// Whether this is the subscription ID of the object at `key` in the bucket at `bucket`.
+    pub fn is_for_bucket_object(&self, bucket: &Address, key: &[u8]) -> bool {
+        self.to_bytes() == bucket_subscription_id(bucket, key).as_slice()
+    }
+
+    /// Returns the raw bytes, as passed to `addBlob`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            SubscriptionId::Default => Vec::new(),
+            SubscriptionId::Named(s) => s.as_bytes().to_vec(),
+            SubscriptionId::Bucket(id) => id.to_vec(),
+            SubscriptionId::Opaque(bytes) => bytes.clone(),
+        }
+    }
+}
+
+impl fmt::Display for SubscriptionId {
+    /// Prints named IDs as is, the default ID as `default`, and raw bytes as `0x`-prefixed hex.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubscriptionId::Default => f.write_str("default"),
+            SubscriptionId::Named(s) => f.write_str(s),
+            SubscriptionId::Bucket(id) => write!(f, "{}", id),
+            SubscriptionId::Opaque(bytes) => f.write_str(&hex::encode_prefixed(bytes)),
+        }
+    }
+}
+
+/// Returns `blake3(bucket_address + object_key)`, the subscription ID of blobs added through a
+/// bucket.
+///
+/// Like the bucket actor, this hashes the raw payload of the bucket's FVM address, e.g. the 20
+/// bytes of its `f2` robust address, not the protocol byte. The EVM address of a bucket is an
+/// ID-masked alias, which hashes differently.
+pub fn bucket_subscription_id(bucket: &Address, key: &[u8]) -> B256 {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&bucket.payload_bytes());
+    hasher.update(key);
+    B256::from(*hasher.finalize().as_bytes())
+}
+
+fn is_readable(s: &str) -> bool {
+    !s.chars().any(|c| c.is_control() || c == char::REPLACEMENT_CHARACTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::b256;
+
+    use super::*;
+
+    fn actor_address() -> Address {
+        let mut bytes = vec![2];
+        bytes.extend(1..=20);
+        Address::from_bytes(&bytes).unwrap()
+    }
+
+    #[test]
+    fn bucket_id_known_answers() {
+        // blake3(01..14 || "foo/bar.txt"), the payload of an `f2` address and a key.
+        let id = bucket_subscription_id(&actor_address(), b"foo/bar.txt");
+        assert_eq!(id, b256!("54af4821573c3f7d4e0ecb4145c5b5196d9a395c05b8a4d364252a6cc0ac23be"));
+        // blake3(d2 09), the LEB128 payload of `f01234`, with an empty key.
+        let id = bucket_subscription_id(&Address::new_id(1234), b"");
+        assert_eq!(id, b256!("40c88f4c8fde838a5ffb55d77249d8c99ee8e38a322609fffc1afb7ab5109e8c"));
+    }
+
+    #[test]
+    fn matches_bucket_objects() {
+        let bucket = actor_address();
+        let id = SubscriptionId::for_bucket_object(&bucket, b"foo/bar.txt");
+        assert!(id.is_bucket_derived());
+        assert!(id.is_for_bucket_object(&bucket, b"foo/bar.txt"));
+        assert!(!id.is_for_bucket_object(&bucket, b"foo/bar.txt2"));
+        assert!(!id.is_for_bucket_object(&Address::new_id(1234), b"foo/bar.txt"));
+        // Classifying the raw bytes again keeps the match.
+        let parsed = SubscriptionId::from_bytes(&id.to_bytes());
+        assert_eq!(parsed, id);
+        assert!(!SubscriptionId::Default.is_for_bucket_object(&bucket, b""));
+    }
+
+    #[test]
+    fn classifies_bytes() {
+        assert_eq!(SubscriptionId::from_bytes(b""), SubscriptionId::Default);
+        assert_eq!(SubscriptionId::parse("backup"), SubscriptionId::Named("backup".to_string()));
+        let hash = [0xffu8; 32];
+        assert_eq!(SubscriptionId::from_bytes(&hash), SubscriptionId::Bucket(B256::from(hash)));
+        // Only 32 bytes can be a hash.
+        assert_eq!(SubscriptionId::from_bytes(&hash[..31]), SubscriptionId::Opaque(vec![0xff; 31]));
+        assert_eq!(
+            SubscriptionId::from_bytes(&[0xff; 33]),
+            SubscriptionId::Opaque(vec![0xff; 33])
+        );
+        // 32 bytes of readable UTF-8 are named, but unreadable UTF-8 is a hash.
+        let readable = "a".repeat(32);
+        assert_eq!(SubscriptionId::parse(&readable), SubscriptionId::Named(readable));
+        let control = [0x01u8; 32];
+        assert!(SubscriptionId::from_bytes(&control).is_bucket_derived());
+        assert_eq!(SubscriptionId::from_bytes(&[0x01; 31]), SubscriptionId::Opaque(vec![1; 31]));
+    }
+
+    #[test]
+    fn lossy_bucket_id_is_opaque() {
+        // A bucket-derived ID decoded as a string, as seen in `BlobTypes.sol`. Each invalid
+        // byte became U+FFFD, so the hash is lost.
+        let lossy = concat!(
+            "\u{fffd}\u{fffd}0\u{fffd}\u{fffd}\u{fffd}\u{fffd}\u{fffd}\u{fffd}\u{48f1}p",
+            "\u{fffd}V\u{fffd}%\u{fffd}\u{fffd}\u{fffd}?\u{fffd}\u{fffd}:\u{8}4T\u{fffd}~",
+            "\u{fffd}\u{fffd}V",
+        );
+        let id = SubscriptionId::parse(lossy);
+        assert!(matches!(id, SubscriptionId::Opaque(_)));
+        assert!(!id.is_bucket_derived());
+        // The raw bytes it came from have the same shape: 32 bytes, mostly invalid UTF-8.
+        let raw: Vec<u8> = lossy
+            .chars()
+            .flat_map(|c| match c {
+                char::REPLACEMENT_CHARACTER => vec![0xff],
+                c => c.to_string().into_bytes(),
+            })
+            .collect();
+        assert_eq!(raw.len(), 32);
+        assert!(SubscriptionId::from_bytes(&raw).is_bucket_derived());
+    }
+
+    #[test]
+    fn renders_hex() {
+        let id = SubscriptionId::for_bucket_object(&Address::new_id(1234), b"");
+        assert_eq!(
+            id.to_string(),
+            "0x40c88f4c8fde838a5ffb55d77249d8c99ee8e38a322609fffc1afb7ab5109e8c"
+        );
+        assert_eq!(SubscriptionId::Default.to_string(), "default");
+        assert_eq!(SubscriptionId::parse("backup").to_string(), "backup");
+        assert_eq!(SubscriptionId::from_bytes(&[0xff, 0x00]).to_string(), "0xff00");
+        for id in [SubscriptionId::Default, SubscriptionId::parse("a b"), id] {
+            assert_eq!(SubscriptionId::from_bytes(&id.to_bytes()), id);
+        }
+    }
+}